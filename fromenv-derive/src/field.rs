@@ -24,9 +24,23 @@ pub enum EnvAttribute {
         from: LitStr,
         default: Option<LitStr>,
         with: Option<ExprPath>,
+        aliases: Vec<LitStr>,
+        file_fallback: bool,
+        os: bool,
+        sensitive: bool,
+        sep: Option<LitStr>,
+        kv_sep: Option<LitStr>,
+        unit: Option<LitStr>,
+    },
+    /// #[env(prefix_map = "...")]
+    PrefixMap {
+        prefix: LitStr,
+        with: Option<ExprPath>,
+        lowercase: bool,
+        sensitive: bool,
     },
     /// #[env(nested)]
-    Nested,
+    Nested { prefix: Option<LitStr> },
     /// No config attr.
     None,
 }
@@ -46,10 +60,27 @@ impl FromField for FromEnvFieldReceiver {
         let mut from: Option<Override<LitStr>> = None;
         let mut default: Option<LitStr> = None;
         let mut with: Option<ExprPath> = None;
+        let mut aliases: Vec<LitStr> = Vec::new();
         let mut nested = Flag::default();
+        let mut file_fallback = Flag::default();
+        let mut os = Flag::default();
+        let mut sensitive = Flag::default();
+        let mut prefix: Option<LitStr> = None;
+        let mut sep: Option<LitStr> = None;
+        let mut kv_sep: Option<LitStr> = None;
+        let mut unit: Option<LitStr> = None;
+        let mut prefix_map: Option<LitStr> = None;
+        let mut lowercase = Flag::default();
 
         let mut default_path_span = Span::call_site();
         let mut with_path_span = Span::call_site();
+        let mut prefix_path_span = Span::call_site();
+        let mut alias_path_span = Span::call_site();
+        let mut sep_path_span = Span::call_site();
+        let mut kv_sep_path_span = Span::call_site();
+        let mut unit_path_span = Span::call_site();
+        let mut from_path_span = Span::call_site();
+        let mut prefix_map_path_span = Span::call_site();
 
         let mut doc_attrs = Vec::new();
 
@@ -81,6 +112,7 @@ impl FromField for FromEnvFieldReceiver {
                     };
 
                     if meta.path().is_ident("from") {
+                        from_path_span = meta.path().span();
                         match FromMeta::from_meta(&meta) {
                             Ok(v) => from = Some(v),
                             Err(e) => {
@@ -103,6 +135,16 @@ impl FromField for FromEnvFieldReceiver {
                                 accumulator.push(e);
                             }
                         }
+                    } else if meta.path().is_ident("alias") {
+                        if aliases.is_empty() {
+                            alias_path_span = meta.path().span();
+                        }
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => aliases.push(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
                     } else if meta.path().is_ident("nested") {
                         match FromMeta::from_meta(&meta) {
                             Ok(v) => nested = v,
@@ -110,6 +152,74 @@ impl FromField for FromEnvFieldReceiver {
                                 accumulator.push(e);
                             }
                         }
+                    } else if meta.path().is_ident("file_fallback") {
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => file_fallback = v,
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("prefix") {
+                        prefix_path_span = meta.path().span();
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => prefix = Some(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("os") {
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => os = v,
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("sensitive") {
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => sensitive = v,
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("sep") {
+                        sep_path_span = meta.path().span();
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => sep = Some(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("prefix_map") {
+                        prefix_map_path_span = meta.path().span();
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => prefix_map = Some(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("lowercase") {
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => lowercase = v,
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("unit") {
+                        unit_path_span = meta.path().span();
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => unit = Some(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
+                    } else if meta.path().is_ident("kv_sep") {
+                        kv_sep_path_span = meta.path().span();
+                        match FromMeta::from_meta(&meta) {
+                            Ok(v) => kv_sep = Some(v),
+                            Err(e) => {
+                                accumulator.push(e);
+                            }
+                        }
                     } else {
                         accumulator.push(
                             darling::Error::unknown_field_path(meta.path()).with_span(&meta.span()),
@@ -122,78 +232,304 @@ impl FromField for FromEnvFieldReceiver {
         }
 
         const NESTED_CLASH: &str = "`nested` cannot be used with other attributes";
-        const DEFAULT_WITHOUT_ENV: &str = "`default` cannot be used without `from`";
-        const WITH_WITHOUT_ENV: &str = "`with` cannot be used without `from`";
         const OPTION_WIH_DEFAULT: &str = "Optional fields cannot have a default";
+        const WITH_OS: &str = "`with` cannot be used with `os`";
+        const PREFIX_WITHOUT_NESTED: &str = "`prefix` cannot be used without `nested`";
+        const SEP_OS: &str = "`sep` cannot be used with `os`";
+        const EMPTY_SEP: &str = "`sep` cannot be empty";
+        const KV_SEP_WITHOUT_SEP: &str = "`kv_sep` cannot be used without `sep`";
+        const EMPTY_KV_SEP: &str = "`kv_sep` cannot be empty";
+        const KV_SEP_SAME_AS_SEP: &str = "`kv_sep` must be different from `sep`";
+        const UNIT_WITHOUT_DURATION: &str =
+            "`unit` can only be used with `with = fromenv::duration`";
+        const FROM_PREFIX_MAP: &str = "`from` cannot be used with `prefix_map`";
+        const OPTIONAL_PREFIX_MAP: &str = "`prefix_map` fields cannot be optional";
+        const EMPTY_PREFIX_MAP: &str = "`prefix_map` cannot be empty";
+        const LOWERCASE_WITHOUT_PREFIX_MAP: &str =
+            "`lowercase` cannot be used without `prefix_map`";
 
-        match (from, default, with, nested.is_present()) {
-            (Some(_), _, _, true) | (_, Some(_), _, true) | (_, _, Some(_), true) => {
+        // Options that are only meaningful when reading from an env var.
+        let flat_options = [
+            ("default", default.is_some(), default_path_span),
+            ("with", with.is_some(), with_path_span),
+            ("alias", !aliases.is_empty(), alias_path_span),
+            (
+                "file_fallback",
+                file_fallback.is_present(),
+                file_fallback.span(),
+            ),
+            ("os", os.is_present(), os.span()),
+            ("sensitive", sensitive.is_present(), sensitive.span()),
+            ("sep", sep.is_some(), sep_path_span),
+            ("kv_sep", kv_sep.is_some(), kv_sep_path_span),
+            ("unit", unit.is_some(), unit_path_span),
+        ];
+
+        if nested.is_present() {
+            if from.is_some()
+                || prefix_map.is_some()
+                || lowercase.is_present()
+                || flat_options.iter().any(|(_, present, _)| *present)
+            {
                 accumulator.push(darling::Error::custom(NESTED_CLASH).with_span(&nested.span()));
 
-                Err(accumulator.finish().unwrap_err())
+                return Err(accumulator.finish().unwrap_err());
             }
-            (None, Some(_), None, false) => {
-                accumulator.push(
-                    darling::Error::custom(DEFAULT_WITHOUT_ENV).with_span(&default_path_span),
-                );
 
-                Err(accumulator.finish().unwrap_err())
+            return accumulator.finish_with(Self {
+                ident,
+                ty,
+                option,
+                doc_attrs,
+                env_attr: EnvAttribute::Nested { prefix },
+            });
+        }
+
+        if prefix.is_some() {
+            let err = darling::Error::custom(PREFIX_WITHOUT_NESTED).with_span(&prefix_path_span);
+
+            accumulator.push(err);
+        }
+
+        if let Some(prefix_map) = prefix_map {
+            if from.is_some() {
+                let err = darling::Error::custom(FROM_PREFIX_MAP).with_span(&from_path_span);
+
+                accumulator.push(err);
             }
-            (None, None, Some(_), false) => {
-                accumulator
-                    .push(darling::Error::custom(WITH_WITHOUT_ENV).with_span(&with_path_span));
 
-                Err(accumulator.finish().unwrap_err())
+            // Only the parser and sensitivity apply to the variables of a
+            // `prefix_map` field.
+            for (name, present, span) in flat_options {
+                if present && !matches!(name, "with" | "sensitive") {
+                    let err = darling::Error::custom(format!(
+                        "`{name}` cannot be used with `prefix_map`"
+                    ))
+                    .with_span(&span);
+
+                    accumulator.push(err);
+                }
             }
-            (None, Some(_), Some(_), false) => {
-                accumulator.push(
-                    darling::Error::custom(DEFAULT_WITHOUT_ENV).with_span(&default_path_span),
-                );
-                accumulator
-                    .push(darling::Error::custom(WITH_WITHOUT_ENV).with_span(&with_path_span));
-
-                Err(accumulator.finish().unwrap_err())
+
+            if option.is_some() {
+                let err =
+                    darling::Error::custom(OPTIONAL_PREFIX_MAP).with_span(&prefix_map_path_span);
+
+                accumulator.push(err);
             }
-            (None, None, None, false) => accumulator.finish_with(Self {
+
+            if prefix_map.value().is_empty() {
+                let err = darling::Error::custom(EMPTY_PREFIX_MAP).with_span(&prefix_map);
+
+                accumulator.push(err);
+            }
+
+            return accumulator.finish_with(Self {
                 ident,
                 ty,
                 option,
                 doc_attrs,
-                env_attr: EnvAttribute::None,
-            }),
-            (None, None, None, true) => accumulator.finish_with(Self {
+                env_attr: EnvAttribute::PrefixMap {
+                    prefix: prefix_map,
+                    with,
+                    lowercase: lowercase.is_present(),
+                    sensitive: sensitive.is_present(),
+                },
+            });
+        }
+
+        if lowercase.is_present() {
+            let err =
+                darling::Error::custom(LOWERCASE_WITHOUT_PREFIX_MAP).with_span(&lowercase.span());
+
+            accumulator.push(err);
+        }
+
+        let Some(from) = from else {
+            for (name, present, span) in flat_options {
+                if present {
+                    let err =
+                        darling::Error::custom(format!("`{name}` cannot be used without `from`"))
+                            .with_span(&span);
+
+                    accumulator.push(err);
+                }
+            }
+
+            return accumulator.finish_with(Self {
                 ident,
                 ty,
                 option,
                 doc_attrs,
-                env_attr: EnvAttribute::Nested,
-            }),
-            (Some(from), default, with, false) => {
-                if option.is_some() && default.is_some() {
-                    let err =
-                        darling::Error::custom(OPTION_WIH_DEFAULT).with_span(&default_path_span);
+                env_attr: EnvAttribute::None,
+            });
+        };
 
-                    accumulator.push(err);
-                }
+        if option.is_some() && default.is_some() {
+            let err = darling::Error::custom(OPTION_WIH_DEFAULT).with_span(&default_path_span);
+
+            accumulator.push(err);
+        }
+
+        if os.is_present() && with.is_some() {
+            let err = darling::Error::custom(WITH_OS).with_span(&with_path_span);
+
+            accumulator.push(err);
+        }
+
+        if os.is_present() && sep.is_some() {
+            let err = darling::Error::custom(SEP_OS).with_span(&sep_path_span);
+
+            accumulator.push(err);
+        }
+
+        if let Some(sep) = sep.as_ref().filter(|sep| sep.value().is_empty()) {
+            let err = darling::Error::custom(EMPTY_SEP).with_span(sep);
+
+            accumulator.push(err);
+        }
+
+        if sep.is_none() && kv_sep.is_some() {
+            let err = darling::Error::custom(KV_SEP_WITHOUT_SEP).with_span(&kv_sep_path_span);
+
+            accumulator.push(err);
+        }
+
+        if let Some(kv_sep) = kv_sep.as_ref().filter(|kv_sep| kv_sep.value().is_empty()) {
+            let err = darling::Error::custom(EMPTY_KV_SEP).with_span(kv_sep);
 
-                let from = from.unwrap_or_else(|| {
-                    LitStr::new(&ident.to_string().to_uppercase(), ident.span())
-                });
-
-                accumulator.finish_with(Self {
-                    ident,
-                    ty,
-                    option,
-                    doc_attrs,
-                    env_attr: EnvAttribute::Flat {
-                        from,
-                        default,
-                        with,
-                    },
-                })
+            accumulator.push(err);
+        }
+
+        if let (Some(sep), Some(kv_sep)) = (&sep, &kv_sep) {
+            if !sep.value().is_empty() && sep.value() == kv_sep.value() {
+                let err = darling::Error::custom(KV_SEP_SAME_AS_SEP).with_span(kv_sep);
+
+                accumulator.push(err);
             }
         }
+
+        let with_duration = with
+            .as_ref()
+            .and_then(BuiltinParser::from_path)
+            .is_some_and(|parser| parser == BuiltinParser::Duration);
+
+        if unit.is_some() && !with_duration {
+            let err = darling::Error::custom(UNIT_WITHOUT_DURATION).with_span(&unit_path_span);
+
+            accumulator.push(err);
+        }
+
+        if let Some(unit) = unit
+            .as_ref()
+            .filter(|unit| !DURATION_UNITS.contains(&unit.value().as_str()))
+        {
+            let err = darling::Error::custom(format!(
+                "unknown unit `{}`, expected one of {}",
+                unit.value(),
+                DURATION_UNITS.join(", ")
+            ))
+            .with_span(unit);
+
+            accumulator.push(err);
+        }
+
+        let from =
+            from.unwrap_or_else(|| LitStr::new(&ident.to_string().to_uppercase(), ident.span()));
+
+        let sensitive = sensitive.is_present() || is_secret(option.as_ref().unwrap_or(&ty));
+
+        accumulator.finish_with(Self {
+            ident,
+            ty,
+            option,
+            doc_attrs,
+            env_attr: EnvAttribute::Flat {
+                from,
+                default,
+                with,
+                aliases,
+                file_fallback: file_fallback.is_present(),
+                os: os.is_present(),
+                sensitive,
+                sep,
+                kv_sep,
+                unit,
+            },
+        })
+    }
+}
+
+/// The parsers provided by the runtime that a `with` path can name.
+///
+/// `from_str` and `into` are recognised by name alone. `duration` and
+/// `byte_size` have to be written as `fromenv::duration` and
+/// `fromenv::byte_size`, so that a function of the same name in scope is used
+/// as a custom parser rather than being replaced by the built in one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuiltinParser {
+    FromStr,
+    Into,
+    Duration,
+    ByteSize,
+}
+
+impl BuiltinParser {
+    pub fn from_path(path: &ExprPath) -> Option<Self> {
+        if path.qself.is_some()
+            || path
+                .path
+                .segments
+                .iter()
+                .any(|segment| !segment.arguments.is_none())
+        {
+            return None;
+        }
+
+        let segments: Vec<String> = path
+            .path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+        let leading_colon = path.path.leading_colon.is_some();
+
+        match (leading_colon, segments.as_slice()) {
+            (false, ["from_str"]) => Some(Self::FromStr),
+            (false, ["into"]) => Some(Self::Into),
+            (_, ["fromenv", "duration"]) => Some(Self::Duration),
+            (_, ["fromenv", "byte_size"]) => Some(Self::ByteSize),
+            _ => None,
+        }
     }
+
+    /// The name reported as the parser in the requirements.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FromStr => "from_str",
+            Self::Into => "into",
+            Self::Duration => "fromenv::duration",
+            Self::ByteSize => "fromenv::byte_size",
+        }
+    }
+}
+
+/// The units accepted by the runtime's `duration` parser.
+const DURATION_UNITS: &[&str] = &["ns", "us", "µs", "ms", "s", "m", "h", "d"];
+
+/// Types from the `secrecy` crate are always treated as sensitive.
+fn is_secret(ty: &Type) -> bool {
+    let Type::Path(type_path) = ty else {
+        return false;
+    };
+
+    type_path.path.segments.last().is_some_and(|segment| {
+        matches!(
+            segment.ident.to_string().as_str(),
+            "Secret" | "SecretBox" | "SecretString" | "SecretSlice"
+        )
+    })
 }
 
 fn parse_option(ty: &Type) -> Option<&Type> {
@@ -6,9 +6,12 @@ use darling::{
 };
 use proc_macro2::TokenStream;
 use quote::{ToTokens, format_ident, quote};
-use syn::{DeriveInput, ExprPath, Ident, Visibility, parse_macro_input};
+use syn::{
+    Attribute, DeriveInput, Expr, ExprLit, ExprPath, Ident, Lit, LitStr, Meta, MetaNameValue,
+    Visibility, parse_macro_input,
+};
 
-use crate::field::{EnvAttribute, FromEnvFieldReceiver};
+use crate::field::{BuiltinParser, EnvAttribute, FromEnvFieldReceiver};
 
 #[proc_macro_derive(FromEnv, attributes(env))]
 pub fn derive_from_env(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
@@ -34,17 +37,21 @@ fn impl_derive(input: DeriveInput) -> darling::Result<TokenStream> {
 }
 
 #[derive(FromDeriveInput)]
-#[darling(supports(struct_named))]
+#[darling(supports(struct_named), forward_attrs(doc))]
 struct FromEnvReceiver {
     pub ident: Ident,
     pub vis: Visibility,
+    pub attrs: Vec<Attribute>,
     pub data: Data<(), FromEnvFieldReceiver>,
 }
 
 struct ConstTokens {
     private_path: TokenStream,
     errors_ident: TokenStream,
+    warnings_ident: TokenStream,
+    context_ident: TokenStream,
     builder_name: Ident,
+    struct_doc: TokenStream,
 }
 
 impl ToTokens for FromEnvReceiver {
@@ -53,8 +60,10 @@ impl ToTokens for FromEnvReceiver {
             builder_name: format_ident!("{}Builder", self.ident),
             private_path: quote!(__fromenv::__private),
             errors_ident: quote!(__fromenv_derive_builder_errors),
+            warnings_ident: quote!(__fromenv_derive_builder_warnings),
+            context_ident: quote!(__fromenv_derive_builder_context),
+            struct_doc: optional_str(doc_string(&self.attrs)),
         };
-        let private_path = &consts.private_path;
 
         let impl_struct = self.impl_struct(&consts);
         let builder_struct = self.builder_struct(&consts);
@@ -65,7 +74,6 @@ impl ToTokens for FromEnvReceiver {
         let derive = quote! {
             const _: () = {
                 extern crate fromenv as __fromenv;
-                use #private_path::Parser as _;
 
                 #impl_struct
 
@@ -97,41 +105,77 @@ impl FromEnvReceiver {
     fn builder_struct(&self, consts: &ConstTokens) -> TokenStream {
         let private_path = &consts.private_path;
         let builder_name = &consts.builder_name;
+        let context_ident = &consts.context_ident;
         let fields = self.get_fields().iter().map(|field| {
             let ident = &field.ident;
             let ty = &field.option.as_ref().unwrap_or(&field.ty);
 
             match &field.env_attr {
-                EnvAttribute::Nested => {
-                    quote! { #ident: Option<<#ty as #private_path::FromEnv>::FromEnvBuilder> }
+                EnvAttribute::Nested { .. } => {
+                    quote! { #ident: Option<<#ty as #private_path::FromEnv>::FromEnvBuilder<'a>> }
                 }
-                EnvAttribute::Flat { .. } | EnvAttribute::None => {
+                EnvAttribute::Flat { .. } | EnvAttribute::PrefixMap { .. } | EnvAttribute::None => {
                     quote! { #ident: Option<#ty> }
                 }
             }
         });
 
         quote! {
-            pub struct #builder_name {
+            pub struct #builder_name<'a> {
                 #(#fields,)*
+                #context_ident: #private_path::Context<'a>,
             }
         }
     }
 
     fn impl_struct(&self, consts: &ConstTokens) -> TokenStream {
         let struct_name = &self.ident;
+        let struct_name_str = struct_name.to_string();
         let private_path = &consts.private_path;
         let builder_name = &consts.builder_name;
 
         quote! {
             impl #struct_name {
-                pub fn from_env() -> #builder_name {
-                    <Self as #private_path::FromEnv>::from_env()
+                pub fn from_env() -> #builder_name<'static> {
+                    Self::from_source(&#private_path::ProcessEnv)
+                }
+
+                pub fn from_source(source: &dyn #private_path::EnvSource) -> #builder_name<'_> {
+                    Self::from_source_with_prefix(source, "")
+                }
+
+                pub fn from_env_with_prefix(prefix: &str) -> #builder_name<'static> {
+                    Self::from_source_with_prefix(&#private_path::ProcessEnv, prefix)
+                }
+
+                pub fn from_source_with_prefix<'a>(
+                    source: &'a dyn #private_path::EnvSource,
+                    prefix: &str,
+                ) -> #builder_name<'a> {
+                    <Self as #private_path::FromEnv>::from_context(#private_path::Context::new(source, prefix, #struct_name_str))
                 }
 
                 pub fn requirements() -> String {
-                    let mut requirements = ::std::string::String::new();
-                    <Self as #private_path::FromEnv>::requirements(&mut requirements);
+                    Self::requirements_with_prefix("")
+                }
+
+                pub fn requirements_with_prefix(prefix: &str) -> String {
+                    #private_path::render_env(&Self::requirements_model_with_prefix(prefix))
+                }
+
+                pub fn json_schema() -> String {
+                    #private_path::render_json_schema(&Self::requirements_model())
+                }
+
+                pub fn requirements_model() -> ::std::vec::Vec<#private_path::Requirement> {
+                    Self::requirements_model_with_prefix("")
+                }
+
+                pub fn requirements_model_with_prefix(prefix: &str) -> ::std::vec::Vec<#private_path::Requirement> {
+                    // The source is never read when collecting requirements.
+                    let context = #private_path::Context::new(&#private_path::ProcessEnv, prefix, <Self as #private_path::FromEnv>::NAME);
+                    let mut requirements = ::std::vec::Vec::new();
+                    <Self as #private_path::FromEnv>::requirements(&context, &mut requirements);
                     requirements
                 }
             }
@@ -140,63 +184,91 @@ impl FromEnvReceiver {
 
     fn impl_from_env(&self, consts: &ConstTokens) -> TokenStream {
         let struct_name = &self.ident;
+        let struct_name_str = struct_name.to_string();
         let builder_name = &consts.builder_name;
         let private_path = &consts.private_path;
+        let context_ident = &consts.context_ident;
 
         let fields = self.get_fields().iter().map(|field| {
             let ident = &field.ident;
             let ty = field.option.as_ref().unwrap_or(&field.ty);
 
             match &field.env_attr {
-                EnvAttribute::Nested => {
-                    quote! { #ident: Some(<#ty as #private_path::FromEnv>::from_env()) }
+                EnvAttribute::Nested { prefix } => {
+                    let prefix = prefix.as_ref().map(LitStr::value).unwrap_or_default();
+                    let field_name = ident.to_string();
+
+                    quote! {
+                        #ident: Some(<#ty as #private_path::FromEnv>::from_context(context.nested(#prefix, #field_name)))
+                    }
                 }
-                EnvAttribute::Flat { .. } | EnvAttribute::None => {
+                EnvAttribute::Flat { .. } | EnvAttribute::PrefixMap { .. } | EnvAttribute::None => {
                     quote! { #ident: None }
                 }
             }
         });
 
         let requirements = self.get_fields().iter().map(|field| {
+            let ident = &field.ident;
             let ty = field.option.as_ref().unwrap_or(&field.ty);
 
             match &field.env_attr {
-                EnvAttribute::Nested => {
+                EnvAttribute::Nested { prefix } => {
+                    let prefix = prefix.as_ref().map(LitStr::value).unwrap_or_default();
+
+                    let field_name = ident.to_string();
+
+                    if field.option.is_some() {
+                        quote! {
+                            let start = requirements.len();
+                            <#ty as #private_path::FromEnv>::requirements(&context.nested(#prefix, #field_name), requirements);
+                            for requirement in &mut requirements[start..] {
+                                requirement.optional = true;
+                            }
+                        }
+                    } else {
+                        quote! {
+                            <#ty as #private_path::FromEnv>::requirements(&context.nested(#prefix, #field_name), requirements);
+                        }
+                    }
+                }
+                EnvAttribute::Flat { .. } => {
+                    let path = format!("{struct_name}.{ident}");
+                    let flat_field = flat_field(consts, field, &path);
+
                     quote! {
-                        <#ty as #private_path::FromEnv>::requirements(requirements);
+                        requirements.push(#flat_field.requirement(context));
                     }
                 }
-                EnvAttribute::Flat {
-                    from,
-                    default,
-                    with: _,
-                } => {
-                    let from = from.value();
-                    let default = default
-                        .as_ref()
-                        .map(|default| default.value())
-                        .unwrap_or(String::new());
-                    let out = format!("{from}={default}\n");
+                EnvAttribute::PrefixMap { .. } => {
+                    let path = format!("{struct_name}.{ident}");
+                    let prefix_map_field = prefix_map_field(consts, field, &path);
 
                     quote! {
-                        requirements.push_str(#out);
+                        requirements.push(#prefix_map_field.requirement(context));
                     }
                 }
-                _ => TokenStream::new(),
+                EnvAttribute::None => TokenStream::new(),
             }
         });
 
         quote! {
             impl #private_path::FromEnv for #struct_name {
-                type FromEnvBuilder = #builder_name;
+                type FromEnvBuilder<'a> = #builder_name<'a>;
+
+                const NAME: &'static str = #struct_name_str;
 
-                fn from_env() -> Self::FromEnvBuilder {
+                fn from_context(context: #private_path::Context<'_>) -> Self::FromEnvBuilder<'_> {
                     #builder_name {
                         #(#fields,)*
+                        #context_ident: context,
                     }
                 }
 
-                fn requirements(requirements: &mut ::std::string::String) {
+                fn requirements(
+                    context: &#private_path::Context<'_>,
+                    requirements: &mut ::std::vec::Vec<#private_path::Requirement>,
+                ) {
                     #(#requirements)*
                 }
             }
@@ -207,20 +279,26 @@ impl FromEnvReceiver {
         let struct_name = &self.ident;
         let private_path = &consts.private_path;
         let errors_ident = &consts.errors_ident;
+        let warnings_ident = &consts.warnings_ident;
         let builder_name = &consts.builder_name;
+        let context_ident = &consts.context_ident;
 
         // This is the secret sauce that lets us gather all errors before
         // failing.
         let assignments = self.get_fields().iter().map(|field| {
             let ident = &field.ident;
             let path = format!("{struct_name}.{ident}");
+            let field_name = ident.to_string();
 
             match (&field.env_attr, field.option.is_some()) {
                 // #[config(nested)] field: T,
-                (EnvAttribute::Nested, false) => {
+                (EnvAttribute::Nested { .. }, false) => {
                     quote! {
-                        let #ident = match #private_path::FromEnvBuilder::finalize(self.#ident.take().unwrap()) {
-                            Ok(inner) => Ok(inner),
+                        let #ident = match #private_path::FromEnvBuilder::finalize_with_report(self.#ident.take().unwrap()) {
+                            Ok((inner, warnings)) => {
+                                #warnings_ident.extend(warnings);
+                                Ok(inner)
+                            }
                             Err(errors) => {
                                 #errors_ident.extend(errors);
                                 Err(())
@@ -229,11 +307,19 @@ impl FromEnvReceiver {
                     }
                 }
                 // #[config(nested)] field: Option<T>,
-                (EnvAttribute::Nested, true) => {
+                (EnvAttribute::Nested { .. }, true) => {
                     quote! {
-                        let #ident = match #private_path::FromEnvBuilder::finalize(self.#ident.take().unwrap()) {
-                            Ok(inner) => Ok(Some(inner)),
-                            Err(errors) if errors.only_missing_errors() => Ok(None),
+                        let #ident = match #private_path::FromEnvBuilder::finalize_with_report(self.#ident.take().unwrap()) {
+                            Ok((inner, warnings)) => {
+                                #warnings_ident.extend(warnings);
+                                Ok(Some(inner))
+                            }
+                            Err(errors) if errors.only_missing_errors() => {
+                                #warnings_ident.add(#private_path::FromEnvWarning::OptionalGroupDropped {
+                                    path: self.#context_ident.field_path(#field_name, #path),
+                                });
+                                Ok(None)
+                            }
                             Err(errors) => {
                                 #errors_ident.extend(errors);
                                 Err(())
@@ -241,137 +327,101 @@ impl FromEnvReceiver {
                         };
                     }
                 }
-                // #[config(env = "...", default = ...)] field: T
-                (
-                    EnvAttribute::Flat {
-                        from,
-                        with,
-                        default: Some(default),
-                    },
-                    false,
-                ) => {
-                    let with = parser_path(consts, with.as_ref());
+                // #[env(from = "...", os)] field: T
+                (EnvAttribute::Flat { os: true, .. }, false) => {
+                    let flat_field = flat_field(consts, field, &path);
 
                     quote! {
-                        let #ident = if let Some(inner) = self.#ident {
-                            Ok(inner)
-                        } else {
-                            match #with.parse_from_env(#from) {
-                                Some((_, Ok(val))) => Ok(val),
-                                Some((value, Err(error))) => {
-                                    let err = #private_path::FromEnvError::ParseError {
-                                        path: #path.to_string(),
-                                        env_var: #from.to_string(),
-                                        value,
-                                        error,
-                                    };
-                                    #errors_ident.add(err);
-                                    Err(())
-                                }
-                                None => {
-                                    #with.parse(#default).map_err(|error| {
-                                        let err = #private_path::FromEnvError::ParseError {
-                                            path: #path.to_string(),
-                                            env_var: #from.to_string(),
-                                            value: #default.to_string(),
-                                            error: error.into(),
-                                        };
-                                        #errors_ident.add(err);
-                                    })
-                                },
-                            }
+                        let #ident = match self.#ident {
+                            Some(inner) => Ok(inner),
+                            None => #flat_field.required_os(&self.#context_ident, &mut #errors_ident, &mut #warnings_ident),
+                        };
+                    }
+                }
+                // #[env(from = "...", os)] field: Option<T>
+                (EnvAttribute::Flat { os: true, .. }, true) => {
+                    let flat_field = flat_field(consts, field, &path);
+
+                    quote! {
+                        let #ident = match self.#ident {
+                            Some(inner) => Ok(Some(inner)),
+                            None => #flat_field.optional_os(&self.#context_ident, &mut #errors_ident, &mut #warnings_ident),
                         };
                     }
                 }
                 // #[env(from = "...")] field: T
                 (
                     EnvAttribute::Flat {
-                        from,
                         with,
-                        default: None,
+                        sep,
+                        kv_sep,
+                        unit,
+                        ..
                     },
                     false,
                 ) => {
-                    let with = parser_path(consts, with.as_ref());
+                    let with = parser_path(
+                        consts,
+                        with.as_ref(),
+                        unit.as_ref(),
+                        sep.as_ref(),
+                        kv_sep.as_ref(),
+                    );
+                    let flat_field = flat_field(consts, field, &path);
 
                     quote! {
-                    let #ident = if let Some(inner) = self.#ident {
-                        Ok(inner)
-                    } else {
-                        match #with.parse_from_env(#from) {
-                            Some((_, Ok(val))) => Ok(val),
-                            Some((value, Err(error))) => {
-                                let err = #private_path::FromEnvError::ParseError {
-                                    path: #path.to_string(),
-                                    env_var: #from.to_string(),
-                                    value,
-                                    error,
-                                };
-                                #errors_ident.add(err);
-                                Err(())
-                            }
-                            None => {
-                                let err = #private_path::FromEnvError::MissingEnv {
-                                    path: #path.to_string(),
-                                    env_var: #from.to_string(),
-                                };
-                                #errors_ident.add(err);
-                                Err(())
-                            }
-                        }
-                    };
+                        let #ident = match self.#ident {
+                            Some(inner) => Ok(inner),
+                            None => #flat_field.required(&self.#context_ident, &#with, &mut #errors_ident, &mut #warnings_ident),
+                        };
                     }
                 }
                 // #[env(from = "...")] field: Option<T>
                 (
                     EnvAttribute::Flat {
-                        from,
                         with,
-                        default: None,
+                        sep,
+                        kv_sep,
+                        unit,
+                        ..
                     },
                     true,
                 ) => {
-                    let with = parser_path(consts, with.as_ref());
+                    let with = parser_path(
+                        consts,
+                        with.as_ref(),
+                        unit.as_ref(),
+                        sep.as_ref(),
+                        kv_sep.as_ref(),
+                    );
+                    let flat_field = flat_field(consts, field, &path);
 
                     quote! {
-                        let #ident = if let Some(inner) = self.#ident {
-                            Ok(Some(inner))
-                        } else {
-                            match #with.parse_from_env(#from) {
-                                Some((_, Ok(val))) => Ok(Some(val)),
-                                Some((value, Err(error))) => {
-                                    let err = #private_path::FromEnvError::ParseError {
-                                        path: #path.to_string(),
-                                        env_var: #from.to_string(),
-                                        value,
-                                        error,
-                                    };
-                                    #errors_ident.add(err);
-                                    Err(())
-                                }
-                                None => {
-                                    Ok(None)
-                                }
-                            }
+                        let #ident = match self.#ident {
+                            Some(inner) => Ok(Some(inner)),
+                            None => #flat_field.optional(&self.#context_ident, &#with, &mut #errors_ident, &mut #warnings_ident),
+                        };
+                    }
+                }
+                // #[env(prefix_map = "...")] field: M
+                (EnvAttribute::PrefixMap { with, .. }, _) => {
+                    let with = parser_path(consts, with.as_ref(), None, None, None);
+                    let prefix_map_field = prefix_map_field(consts, field, &path);
+
+                    quote! {
+                        let #ident = match self.#ident {
+                            Some(inner) => Ok(inner),
+                            None => #prefix_map_field.read(&self.#context_ident, &#with, &mut #errors_ident, &mut #warnings_ident),
                         };
                     }
                 }
-                // #[env(default = "...")] field: Option<T>
-                (
-                    EnvAttribute::Flat {
-                        from: _,
-                        with: _,
-                        default: Some(_),
-                    },
-                    true,
-                ) => unreachable!("we've already checked that Optional fields can't have a default"),
                 (EnvAttribute::None, false) => {
                     quote! {
                         let #ident = match self.#ident {
                             Some(inner) => Ok(inner),
                             None => {
                                 let err = #private_path::FromEnvError::MissingValue {
-                                    path: #path.to_string(),
+                                    path: self.#context_ident.field_path(#field_name, #path),
                                 };
                                 #errors_ident.add(err);
                                 Err(())
@@ -401,17 +451,22 @@ impl FromEnvReceiver {
         });
 
         quote! {
-            impl #private_path::FromEnvBuilder for #builder_name {
+            impl #private_path::FromEnvBuilder for #builder_name<'_> {
                 type Target = #struct_name;
 
-                fn finalize(mut self) -> Result<Self::Target, #private_path::FromEnvErrors> {
+                fn finalize_with_report(
+                    mut self,
+                ) -> Result<(Self::Target, #private_path::FromEnvWarnings), #private_path::FromEnvErrors> {
                     let mut #errors_ident = #private_path::FromEnvErrors::new();
+                    let mut #warnings_ident = #private_path::FromEnvWarnings::new();
 
                     #(#assignments)*
 
-                    Ok(#struct_name {
+                    let target = #struct_name {
                         #(#fields,)*
-                    })
+                    };
+
+                    Ok((target, #warnings_ident))
                 }
             }
         }
@@ -427,12 +482,12 @@ impl FromEnvReceiver {
             let doc_attrs = &field.doc_attrs;
 
             match &field.env_attr {
-                EnvAttribute::Nested => {
+                EnvAttribute::Nested { .. } => {
                     quote! {
                         #(#doc_attrs)*
                         pub fn #ident<F>(mut self, f: F) -> Self
                         where
-                            F: FnOnce(<#ty as #private_path::FromEnv>::FromEnvBuilder) -> <#ty as #private_path::FromEnv>::FromEnvBuilder,
+                            F: FnOnce(<#ty as #private_path::FromEnv>::FromEnvBuilder<'a>) -> <#ty as #private_path::FromEnv>::FromEnvBuilder<'a>,
                         {
                             let nested = self.#ident.take().unwrap();
                             let nested = f(nested);
@@ -441,7 +496,7 @@ impl FromEnvReceiver {
                         }
                     }
                 }
-                EnvAttribute::Flat { .. } | EnvAttribute::None => {
+                EnvAttribute::Flat { .. } | EnvAttribute::PrefixMap { .. } | EnvAttribute::None => {
                     quote! {
                         #(#doc_attrs)*
                         pub fn #ident(mut self, #ident: #ty) -> Self {
@@ -454,12 +509,21 @@ impl FromEnvReceiver {
         });
 
         quote! {
-            impl #builder_name {
+            impl<'a> #builder_name<'a> {
                 #(#setters)*
 
                 pub fn finalize(self) -> Result<<Self as #private_path::FromEnvBuilder>::Target, #private_path::FromEnvErrors> {
                     #private_path::FromEnvBuilder::finalize(self)
                 }
+
+                pub fn finalize_with_report(
+                    self,
+                ) -> Result<
+                    (<Self as #private_path::FromEnvBuilder>::Target, #private_path::FromEnvWarnings),
+                    #private_path::FromEnvErrors,
+                > {
+                    #private_path::FromEnvBuilder::finalize_with_report(self)
+                }
             }
         }
     }
@@ -473,21 +537,202 @@ impl FromEnvReceiver {
     }
 }
 
-fn parser_path(consts: &ConstTokens, path: Option<&ExprPath>) -> TokenStream {
+/// Builds the `FlatField` descriptor used by the generated code to read and
+/// document a `#[env(from)]` field.
+fn flat_field(consts: &ConstTokens, field: &FromEnvFieldReceiver, path: &str) -> TokenStream {
     let private_path = &consts.private_path;
+    let struct_doc = &consts.struct_doc;
+
+    let EnvAttribute::Flat {
+        from,
+        default,
+        with,
+        aliases,
+        file_fallback,
+        os,
+        sensitive,
+        sep,
+        kv_sep,
+        unit,
+    } = &field.env_attr
+    else {
+        panic!("expected a flat field");
+    };
 
-    if let Some(expr_path) = path {
-        if let Some(ident) = expr_path.path.get_ident() {
-            let ident_str = ident.to_string();
+    let field_name = field.ident.to_string();
+    let ty = type_name(&field.ty);
+    let parser = match with {
+        _ if *os => "os".to_owned(),
+        Some(with) => parser_name(with),
+        None => "from_str".to_owned(),
+    };
+    let doc = optional_str(doc_string(&field.doc_attrs));
+    let optional = field.option.is_some();
+    let default = match default {
+        Some(default) => quote!(Some(#default)),
+        None => quote!(None),
+    };
+    let sep = match sep {
+        Some(sep) => quote!(Some(#sep)),
+        None => quote!(None),
+    };
+    let kv_sep = match kv_sep {
+        Some(kv_sep) => quote!(Some(#kv_sep)),
+        None => quote!(None),
+    };
+    let unit = match unit {
+        Some(unit) => quote!(Some(#unit)),
+        None => quote!(None),
+    };
 
-            match ident_str.as_str() {
-                "from_str" => return quote!(#private_path::from_str),
-                "into" => return quote!(#private_path::into),
-                _ => {}
-            }
+    quote! {
+        #private_path::FlatField {
+            field: #field_name,
+            path: #path,
+            env_var: #from,
+            ty: #ty,
+            parser: #parser,
+            doc: #doc,
+            struct_doc: #struct_doc,
+            optional: #optional,
+            default: #default,
+            aliases: &[#(#aliases),*],
+            file_fallback: #file_fallback,
+            sensitive: #sensitive,
+            sep: #sep,
+            kv_sep: #kv_sep,
+            unit: #unit,
         }
+    }
+}
+
+/// Builds the `PrefixMapField` descriptor used by the generated code to read
+/// and document a `#[env(prefix_map = "...")]` field.
+fn prefix_map_field(consts: &ConstTokens, field: &FromEnvFieldReceiver, path: &str) -> TokenStream {
+    let private_path = &consts.private_path;
+    let struct_doc = &consts.struct_doc;
+
+    let EnvAttribute::PrefixMap {
+        prefix,
+        with,
+        lowercase,
+        sensitive,
+    } = &field.env_attr
+    else {
+        panic!("expected a prefix_map field");
+    };
 
-        return quote!(#expr_path);
+    let field_name = field.ident.to_string();
+    let ty = type_name(&field.ty);
+    let parser = match with {
+        Some(with) => parser_name(with),
+        None => "from_str".to_owned(),
+    };
+    let doc = optional_str(doc_string(&field.doc_attrs));
+
+    quote! {
+        #private_path::PrefixMapField {
+            field: #field_name,
+            path: #path,
+            prefix: #prefix,
+            ty: #ty,
+            parser: #parser,
+            doc: #doc,
+            struct_doc: #struct_doc,
+            lowercase: #lowercase,
+            sensitive: #sensitive,
+        }
+    }
+}
+
+/// Renders tokens the way they were most likely written, e.g. `Option<u16>`
+/// rather than `Option < u16 >`.
+fn type_name(tokens: &impl ToTokens) -> String {
+    let mut name = tokens.to_token_stream().to_string();
+
+    for (from, to) in [
+        (" <", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ::", "::"),
+        (":: ", "::"),
+        ("& ", "&"),
+        (" ,", ","),
+    ] {
+        name = name.replace(from, to);
+    }
+
+    name
+}
+
+fn optional_str(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote!(Some(#value)),
+        None => quote!(None),
+    }
+}
+
+/// Joins the lines of a doc comment.
+fn doc_string(doc_attrs: &[Attribute]) -> Option<String> {
+    let lines: Vec<_> = doc_attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(doc), ..
+                    }),
+                ..
+            }) => Some(doc.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_owned).unwrap_or(line))
+        .collect();
+
+    let doc = lines.join("\n").trim().to_owned();
+
+    if doc.is_empty() { None } else { Some(doc) }
+}
+
+/// The name of the parser named by a `with` path, as reported in the
+/// requirements.
+fn parser_name(with: &ExprPath) -> String {
+    match BuiltinParser::from_path(with) {
+        Some(parser) => parser.name().to_owned(),
+        None => type_name(with),
+    }
+}
+
+/// The parser for a flat field. With `sep`, the parser is used for each
+/// element of the list, or each value of the map when `kv_sep` is also given.
+fn parser_path(
+    consts: &ConstTokens,
+    path: Option<&ExprPath>,
+    unit: Option<&LitStr>,
+    sep: Option<&LitStr>,
+    kv_sep: Option<&LitStr>,
+) -> TokenStream {
+    let private_path = &consts.private_path;
+
+    if let Some(sep) = sep {
+        let element = parser_path(consts, path, unit, None, None);
+        return match kv_sep {
+            Some(kv_sep) => quote!(#private_path::Map::new(#sep, #kv_sep, #element)),
+            None => quote!(#private_path::List::new(#sep, #element)),
+        };
+    }
+
+    if let Some(expr_path) = path {
+        return match (BuiltinParser::from_path(expr_path), unit) {
+            (Some(BuiltinParser::FromStr), _) => quote!(#private_path::from_str),
+            (Some(BuiltinParser::Into), _) => quote!(#private_path::into),
+            (Some(BuiltinParser::Duration), Some(unit)) => {
+                quote!(#private_path::DurationWithUnit(#unit))
+            }
+            (Some(BuiltinParser::Duration), None) => quote!(#private_path::duration),
+            (Some(BuiltinParser::ByteSize), _) => quote!(#private_path::byte_size),
+            (None, _) => quote!(#expr_path),
+        };
     }
 
     quote!(#private_path::from_str)
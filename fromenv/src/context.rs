@@ -0,0 +1,44 @@
+use crate::{EnvSource, path::FieldPath};
+
+/// The state shared by a builder and all of its nested builders.
+pub struct Context<'a> {
+    source: &'a dyn EnvSource,
+    prefix: String,
+    path: String,
+}
+
+impl<'a> Context<'a> {
+    /// Creates the context for the root builder, `struct_name` is the root of
+    /// every field path.
+    pub fn new(source: &'a dyn EnvSource, prefix: &str, struct_name: &str) -> Self {
+        Self {
+            source,
+            prefix: prefix.to_owned(),
+            path: struct_name.to_owned(),
+        }
+    }
+
+    /// Creates the context for the builder of the nested struct stored in
+    /// `field`, with `prefix` appended to any existing prefix.
+    pub fn nested(&self, prefix: &str, field: &str) -> Self {
+        Self {
+            source: self.source,
+            prefix: format!("{}{prefix}", self.prefix),
+            path: format!("{}.{field}", self.path),
+        }
+    }
+
+    /// The path to `field`, `local` is the struct-local form of the path.
+    pub fn field_path(&self, field: &str, local: &str) -> FieldPath {
+        FieldPath::new(format!("{}.{field}", self.path), local)
+    }
+
+    pub(crate) fn source(&self) -> &dyn EnvSource {
+        self.source
+    }
+
+    /// The name of the variable to look up, including any prefix.
+    pub(crate) fn env_var(&self, name: &str) -> String {
+        format!("{}{name}", self.prefix)
+    }
+}
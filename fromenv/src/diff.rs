@@ -0,0 +1,387 @@
+use std::fmt;
+
+use crate::requirement::{Format, Requirement};
+
+/// How a change to the requirements affects existing deployments.
+///
+/// Severities are ordered, so the most severe change in a diff can be found
+/// using `max`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Existing deployments are unaffected.
+    Compatible,
+    /// Existing deployments will still load, but may behave differently.
+    Notable,
+    /// Existing deployments may fail to load.
+    Breaking,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compatible => f.write_str("compatible"),
+            Self::Notable => f.write_str("notable"),
+            Self::Breaking => f.write_str("breaking"),
+        }
+    }
+}
+
+/// A change to a single variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ChangeKind {
+    /// The variable is new.
+    Added { required: bool },
+    /// The variable is no longer read, so any value set for it is ignored.
+    Removed,
+    /// The variable used to be optional or have a default, but must now be
+    /// set.
+    BecameRequired,
+    /// The variable used to be required, but is now optional or has a
+    /// default.
+    BecameOptional,
+    /// The variable was renamed from `old`, which is still read as an alias.
+    Renamed { old: String },
+    /// The variable no longer reads `alias`, so a value set for it is ignored.
+    AliasRemoved { alias: String },
+    /// The variable can no longer be read from a file named by `{NAME}_FILE`.
+    FileFallbackRemoved,
+    /// The variable's default changed.
+    DefaultChanged { old: String, new: String },
+    /// The type the variable is parsed into changed, so values that used to
+    /// be accepted may no longer parse.
+    TypeChanged { old: String, new: String },
+    /// The format of the value changed, e.g. a list's separator, so values
+    /// that used to be accepted may no longer parse.
+    FormatChanged {
+        old: Option<Format>,
+        new: Option<Format>,
+    },
+    /// The parser used for the value changed, e.g. a new `with` function, so
+    /// values that used to be accepted may no longer parse.
+    ParserChanged { old: String, new: String },
+    /// The variable was marked or unmarked as `sensitive`, which changes
+    /// whether its value is masked in errors, warnings and documentation.
+    SensitivityChanged { sensitive: bool },
+}
+
+/// A classified change between two sets of requirements.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub env_var: String,
+    pub kind: ChangeKind,
+    pub severity: Severity,
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let env_var = &self.env_var;
+        write!(f, "{}: ", self.severity)?;
+
+        match &self.kind {
+            ChangeKind::Added { required: true } => {
+                write!(f, "'{env_var}' is a new required variable")
+            }
+            ChangeKind::Added { required: false } => {
+                write!(f, "'{env_var}' is a new optional variable")
+            }
+            ChangeKind::Removed => write!(f, "'{env_var}' is no longer used"),
+            ChangeKind::BecameRequired => write!(f, "'{env_var}' is now required"),
+            ChangeKind::BecameOptional => write!(f, "'{env_var}' is no longer required"),
+            ChangeKind::Renamed { old } => {
+                write!(
+                    f,
+                    "'{env_var}' was renamed from '{old}', which is still read"
+                )
+            }
+            ChangeKind::AliasRemoved { alias } => {
+                write!(f, "'{env_var}' no longer reads '{alias}'")
+            }
+            ChangeKind::FileFallbackRemoved => {
+                write!(f, "'{env_var}' can no longer be read from '{env_var}_FILE'")
+            }
+            ChangeKind::DefaultChanged { old, new } => {
+                write!(f, "'{env_var}' default changed from \"{old}\" to \"{new}\"")
+            }
+            ChangeKind::TypeChanged { old, new } => {
+                write!(f, "'{env_var}' type changed from `{old}` to `{new}`")
+            }
+            ChangeKind::FormatChanged { old, new } => write!(
+                f,
+                "'{env_var}' format changed from {} to {}",
+                describe_format(old),
+                describe_format(new)
+            ),
+            ChangeKind::ParserChanged { old, new } => {
+                write!(f, "'{env_var}' parser changed from `{old}` to `{new}`")
+            }
+            ChangeKind::SensitivityChanged { sensitive: true } => {
+                write!(f, "'{env_var}' is now sensitive")
+            }
+            ChangeKind::SensitivityChanged { sensitive: false } => {
+                write!(f, "'{env_var}' is no longer sensitive")
+            }
+        }
+    }
+}
+
+fn describe_format(format: &Option<Format>) -> String {
+    match format {
+        Some(format) => format.to_string(),
+        None => "a single value".to_owned(),
+    }
+}
+
+/// The changes between two sets of requirements, as returned by
+/// `Config::requirements_model()` or read back from a snapshot using the
+/// `serde` feature.
+///
+/// Variables are matched by name. A variable renamed with the old name kept as
+/// an `alias` is matched to its old name, while any other renamed variable is
+/// reported as one variable being removed and another added.
+///
+/// Each variable is compared once, using the first requirement that reads it.
+///
+/// ```rust
+/// use fromenv::{RequirementsDiff, Severity};
+///
+/// mod v1 {
+///     #[derive(fromenv::FromEnv)]
+///     pub struct Config {
+///         #[env(from = "PORT", default = "8080")]
+///         port: u16,
+///     }
+/// }
+///
+/// mod v2 {
+///     #[derive(fromenv::FromEnv)]
+///     pub struct Config {
+///         #[env(from = "PORT", default = "8080")]
+///         port: u16,
+///         #[env(from = "DATABASE_URL")]
+///         database_url: String,
+///     }
+/// }
+///
+/// let diff = RequirementsDiff::between(
+///     &v1::Config::requirements_model(),
+///     &v2::Config::requirements_model(),
+/// );
+///
+/// assert_eq!(Some(Severity::Breaking), diff.severity());
+/// assert_eq!(
+///     "breaking: 'DATABASE_URL' is a new required variable\n",
+///     diff.to_string()
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RequirementsDiff(Vec<Change>);
+
+impl RequirementsDiff {
+    /// Compares the `old` requirements to the `new` ones.
+    pub fn between(old: &[Requirement], new: &[Requirement]) -> Self {
+        let old = unique(old);
+        let new = unique(new);
+        let mut changes = Vec::new();
+
+        for old in &old {
+            match matching(old, &new) {
+                Some(new) => compare(old, new, &mut changes),
+                None => changes.push(Change {
+                    env_var: old.env_var.clone(),
+                    kind: ChangeKind::Removed,
+                    severity: Severity::Notable,
+                }),
+            }
+        }
+
+        for new in &new {
+            if old.iter().all(|old| matching(old, &[new]).is_none()) {
+                let required = new.is_required();
+
+                changes.push(Change {
+                    env_var: new.env_var.clone(),
+                    kind: ChangeKind::Added { required },
+                    severity: if required {
+                        Severity::Breaking
+                    } else {
+                        Severity::Compatible
+                    },
+                });
+            }
+        }
+
+        Self(changes)
+    }
+
+    /// The most severe change, or `None` if nothing changed.
+    pub fn severity(&self) -> Option<Severity> {
+        self.0.iter().map(|change| change.severity).max()
+    }
+
+    /// Whether any change may cause existing deployments to fail to load.
+    pub fn is_breaking(&self) -> bool {
+        self.severity() == Some(Severity::Breaking)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Change> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for RequirementsDiff {
+    type Item = Change;
+    type IntoIter = std::vec::IntoIter<Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a RequirementsDiff {
+    type Item = &'a Change;
+    type IntoIter = std::slice::Iter<'a, Change>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+/// Lists each change on its own line, prefixed by its severity.
+impl fmt::Display for RequirementsDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.0 {
+            writeln!(f, "{change}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Keeps the first requirement for each variable, so that a variable read by
+/// several fields is only compared once.
+fn unique(requirements: &[Requirement]) -> Vec<&Requirement> {
+    let mut unique: Vec<&Requirement> = Vec::new();
+
+    for requirement in requirements {
+        if unique
+            .iter()
+            .all(|existing| existing.env_var != requirement.env_var)
+        {
+            unique.push(requirement);
+        }
+    }
+
+    unique
+}
+
+/// Finds the new requirement for `old`, either by name or by a new variable
+/// that reads `old` as an alias.
+fn matching<'a>(old: &Requirement, new: &[&'a Requirement]) -> Option<&'a Requirement> {
+    new.iter()
+        .find(|new| new.env_var == old.env_var)
+        .or_else(|| new.iter().find(|new| new.aliases.contains(&old.env_var)))
+        .copied()
+}
+
+fn compare(old: &Requirement, new: &Requirement, changes: &mut Vec<Change>) {
+    let mut push = |kind, severity| {
+        changes.push(Change {
+            env_var: new.env_var.clone(),
+            kind,
+            severity,
+        })
+    };
+
+    if old.env_var != new.env_var {
+        push(
+            ChangeKind::Renamed {
+                old: old.env_var.clone(),
+            },
+            Severity::Compatible,
+        );
+    }
+
+    if !old.is_required() && new.is_required() {
+        push(ChangeKind::BecameRequired, Severity::Breaking);
+    } else if old.is_required() && !new.is_required() {
+        push(ChangeKind::BecameOptional, Severity::Compatible);
+    }
+
+    for alias in &old.aliases {
+        if *alias != new.env_var && !new.aliases.contains(alias) {
+            push(
+                ChangeKind::AliasRemoved {
+                    alias: alias.clone(),
+                },
+                Severity::Breaking,
+            );
+        }
+    }
+
+    if old.file_fallback && !new.file_fallback {
+        push(ChangeKind::FileFallbackRemoved, Severity::Breaking);
+    }
+
+    if old.sensitive != new.sensitive {
+        push(
+            ChangeKind::SensitivityChanged {
+                sensitive: new.sensitive,
+            },
+            Severity::Notable,
+        );
+    }
+
+    // The default of a sensitive field is masked, so it can only be compared
+    // when both fields are equally sensitive.
+    if let (Some(old_default), Some(new_default)) = (&old.default, &new.default) {
+        if old.sensitive == new.sensitive && old_default != new_default {
+            push(
+                ChangeKind::DefaultChanged {
+                    old: old_default.clone(),
+                    new: new_default.clone(),
+                },
+                Severity::Notable,
+            );
+        }
+    }
+
+    if old.format != new.format {
+        push(
+            ChangeKind::FormatChanged {
+                old: old.format.clone(),
+                new: new.format.clone(),
+            },
+            Severity::Breaking,
+        );
+    }
+
+    if old.parser != new.parser {
+        push(
+            ChangeKind::ParserChanged {
+                old: old.parser.clone(),
+                new: new.parser.clone(),
+            },
+            Severity::Breaking,
+        );
+    }
+
+    // Whether the variable is optional is covered above, so `Option<T>` and
+    // `T` are treated as the same type.
+    if old.value_type() != new.value_type() {
+        push(
+            ChangeKind::TypeChanged {
+                old: old.ty.clone(),
+                new: new.ty.clone(),
+            },
+            Severity::Breaking,
+        );
+    }
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    EnvSource,
+    error::{FromEnvError, FromEnvErrors},
+};
+
+/// Variables loaded from a `.env` file.
+///
+/// `Dotenv` implements [`EnvSource`] so it can be passed to
+/// `Config::from_source`. Use [`EnvSource::or`] to choose whether it should
+/// rank above or below the process environment:
+///
+/// ```rust,no_run
+/// use fromenv::{Dotenv, EnvSource, FromEnv, ProcessEnv};
+///
+/// #[derive(FromEnv, Debug)]
+/// pub struct Config {
+///     #[env(from)]
+///     database_url: String,
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// // The process environment takes precedence over the `.env` file.
+/// let source = ProcessEnv.or(Dotenv::from_path(".env")?);
+/// let config = Config::from_source(&source).finalize()?;
+///
+/// // The `.env` file takes precedence over the process environment.
+/// let source = Dotenv::from_path(".env")?.or(ProcessEnv);
+/// let config = Config::from_source(&source).finalize()?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Format
+///
+/// * Each line is of the form `KEY=VALUE` and may be prefixed with `export`.
+/// * Blank lines and lines starting with `#` are ignored.
+/// * Unquoted values are trimmed, and anything after a ` #` is treated as a
+///   comment.
+/// * Single quoted values are taken literally.
+/// * Double quoted values support the escapes `\n`, `\r`, `\t`, `\"`, `\\`
+///   and `\$`.
+/// * Quoted values may span multiple lines.
+/// * Variables are _not_ interpolated.
+///
+/// When a key appears more than once, the last value wins.
+#[derive(Debug, Clone)]
+pub struct Dotenv {
+    file: PathBuf,
+    vars: HashMap<String, String>,
+}
+
+impl Dotenv {
+    /// Reads and parses the file at `path`.
+    ///
+    /// All syntax errors in the file are collected and returned together,
+    /// each one identifying the file and line it was found on.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, FromEnvErrors> {
+        let path = path.as_ref();
+
+        match std::fs::read_to_string(path) {
+            Ok(contents) => Self::parse(path, &contents),
+            Err(error) => {
+                let mut errors = FromEnvErrors::new();
+                errors.add(FromEnvError::Dotenv {
+                    file: path.to_owned(),
+                    line: None,
+                    error: error.into(),
+                });
+                Err(errors)
+            }
+        }
+    }
+
+    /// Parses `contents` as a `.env` file, `file` is only used for error
+    /// reporting.
+    pub fn parse(file: impl Into<PathBuf>, contents: &str) -> Result<Self, FromEnvErrors> {
+        let file = file.into();
+        let mut vars = HashMap::new();
+        let mut errors = FromEnvErrors::new();
+
+        let mut lines = contents.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+        while let Some((line_number, line)) = lines.next() {
+            let line = line.trim_start();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            match parse_entry(line, &mut lines) {
+                Ok((key, value)) => {
+                    vars.insert(key, value);
+                }
+                Err(error) => errors.add(FromEnvError::Dotenv {
+                    file: file.clone(),
+                    line: Some(line_number),
+                    error: error.into(),
+                }),
+            }
+        }
+
+        if errors.has_errors() {
+            Err(errors)
+        } else {
+            Ok(Self { file, vars })
+        }
+    }
+
+    /// The path of the file the variables were loaded from.
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+}
+
+impl EnvSource for Dotenv {
+    fn var(&self, key: &str) -> Option<String> {
+        self.vars.get(key).cloned()
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        self.vars.keys().cloned().collect()
+    }
+}
+
+fn parse_entry<'a>(
+    line: &'a str,
+    rest: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<(String, String), String> {
+    let line = match line.strip_prefix("export") {
+        Some(stripped) if stripped.starts_with(char::is_whitespace) => stripped.trim_start(),
+        _ => line,
+    };
+
+    let Some((key, value)) = line.split_once('=') else {
+        return Err("expected `KEY=VALUE`".to_owned());
+    };
+
+    let key = key.trim_end();
+    if !is_valid_key(key) {
+        return Err(format!("invalid variable name `{key}`"));
+    }
+
+    let value = value.trim_start();
+    let value = match value.chars().next() {
+        Some(quote @ ('\'' | '"')) => parse_quoted(&value[1..], quote, rest)?,
+        _ => strip_comment(value).trim_end().to_owned(),
+    };
+
+    Ok((key.to_owned(), value))
+}
+
+fn parse_quoted<'a>(
+    mut line: &'a str,
+    quote: char,
+    rest: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<String, String> {
+    let mut value = String::new();
+
+    loop {
+        let mut chars = line.char_indices();
+
+        while let Some((i, c)) = chars.next() {
+            if c == quote {
+                let trailing = line[i + 1..].trim_start();
+                return if trailing.is_empty() || trailing.starts_with('#') {
+                    Ok(value)
+                } else {
+                    Err(format!(
+                        "unexpected characters after closing quote: `{trailing}`"
+                    ))
+                };
+            }
+
+            if c == '\\' && quote == '"' {
+                match chars.next().map(|(_, c)| c) {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some(c @ ('"' | '\\' | '$')) => value.push(c),
+                    Some(c) => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                    None => value.push('\\'),
+                }
+            } else {
+                value.push(c);
+            }
+        }
+
+        match rest.next() {
+            Some((_, next)) => {
+                value.push('\n');
+                line = next;
+            }
+            None => return Err(format!("unterminated {quote}-quoted value")),
+        }
+    }
+}
+
+fn strip_comment(value: &str) -> &str {
+    let mut previous = None;
+
+    for (i, c) in value.char_indices() {
+        if c == '#' && previous.is_some_and(char::is_whitespace) {
+            return &value[..i];
+        }
+        previous = Some(c);
+    }
+
+    value
+}
+
+fn is_valid_key(key: &str) -> bool {
+    let mut chars = key.chars();
+
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
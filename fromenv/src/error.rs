@@ -1,24 +1,112 @@
-use std::{error::Error as StdError, fmt};
+use std::{error::Error as StdError, fmt, path::PathBuf};
 
-use super::BoxError;
+use crate::{BoxError, path::FieldPath};
 
+/// A single configuration error.
+///
+/// Use the accessor methods to inspect an error without matching on every
+/// variant, for example to emit a structured log line or metric for each
+/// failing variable.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum FromEnvError {
-    MissingEnv {
-        path: String,
-        env_var: String,
-    },
+    /// A required variable wasn't set and the field has no default.
+    MissingEnv { path: FieldPath, env_var: String },
+    /// The variable was set but its value couldn't be parsed.
     ParseError {
-        path: String,
+        path: FieldPath,
         env_var: String,
         value: String,
         error: BoxError,
     },
-    MissingValue {
-        path: String,
+    /// A field without an `#[env]` attribute wasn't provided to the builder.
+    MissingValue { path: FieldPath },
+    /// The variable was set but isn't valid unicode.
+    NotUnicode { path: FieldPath, env_var: String },
+    /// The variable and one of its aliases are both set, with different
+    /// values.
+    AliasConflict {
+        path: FieldPath,
+        env_var: String,
+        alias: String,
+    },
+    /// The `<VAR>_FILE` variable was set but the file couldn't be read.
+    ReadFile {
+        path: FieldPath,
+        env_var: String,
+        file: PathBuf,
+        error: std::io::Error,
+    },
+    /// A `.env` file couldn't be read or parsed.
+    Dotenv {
+        file: PathBuf,
+        line: Option<usize>,
+        error: BoxError,
     },
 }
 
+/// The kind of a [`FromEnvError`], without any of its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FromEnvErrorKind {
+    MissingEnv,
+    ParseError,
+    MissingValue,
+    NotUnicode,
+    AliasConflict,
+    ReadFile,
+    Dotenv,
+}
+
+impl FromEnvError {
+    /// The kind of error.
+    pub fn kind(&self) -> FromEnvErrorKind {
+        match self {
+            Self::MissingEnv { .. } => FromEnvErrorKind::MissingEnv,
+            Self::ParseError { .. } => FromEnvErrorKind::ParseError,
+            Self::MissingValue { .. } => FromEnvErrorKind::MissingValue,
+            Self::NotUnicode { .. } => FromEnvErrorKind::NotUnicode,
+            Self::AliasConflict { .. } => FromEnvErrorKind::AliasConflict,
+            Self::ReadFile { .. } => FromEnvErrorKind::ReadFile,
+            Self::Dotenv { .. } => FromEnvErrorKind::Dotenv,
+        }
+    }
+
+    /// The path to the field the error relates to, or `None` for errors that
+    /// don't relate to a field, such as a malformed `.env` file.
+    pub fn path(&self) -> Option<&FieldPath> {
+        match self {
+            Self::MissingEnv { path, .. }
+            | Self::ParseError { path, .. }
+            | Self::MissingValue { path }
+            | Self::NotUnicode { path, .. }
+            | Self::AliasConflict { path, .. }
+            | Self::ReadFile { path, .. } => Some(path),
+            Self::Dotenv { .. } => None,
+        }
+    }
+
+    /// The name of the variable that caused the error, including any prefix.
+    pub fn env_var(&self) -> Option<&str> {
+        match self {
+            Self::MissingEnv { env_var, .. }
+            | Self::ParseError { env_var, .. }
+            | Self::NotUnicode { env_var, .. }
+            | Self::AliasConflict { env_var, .. }
+            | Self::ReadFile { env_var, .. } => Some(env_var),
+            Self::MissingValue { .. } | Self::Dotenv { .. } => None,
+        }
+    }
+
+    /// The value that failed to parse.
+    pub fn value(&self) -> Option<&str> {
+        match self {
+            Self::ParseError { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+}
+
 impl fmt::Display for FromEnvError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -42,11 +130,61 @@ impl fmt::Display for FromEnvError {
             Self::MissingValue { path } => {
                 write!(f, "`{path}`: No value provided")
             }
+            Self::NotUnicode { path, env_var } => {
+                write!(
+                    f,
+                    "`{path}`: Environment variable '{env_var}' is not valid unicode"
+                )
+            }
+            Self::AliasConflict {
+                path,
+                env_var,
+                alias,
+            } => {
+                write!(
+                    f,
+                    "`{path}`: '{env_var}' and '{alias}' are both set but have different values"
+                )
+            }
+            Self::ReadFile {
+                path,
+                env_var,
+                file,
+                error,
+            } => {
+                write!(
+                    f,
+                    "`{path}`: Failed to read '{env_var}'=\"{}\": {error}",
+                    file.display()
+                )
+            }
+            Self::Dotenv {
+                file,
+                line: Some(line),
+                error,
+            } => {
+                write!(f, "`{}:{line}`: {error}", file.display())
+            }
+            Self::Dotenv {
+                file,
+                line: None,
+                error,
+            } => {
+                write!(f, "`{}`: {error}", file.display())
+            }
         }
     }
 }
 
-impl StdError for FromEnvError {}
+impl StdError for FromEnvError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::ParseError { error, .. } | Self::Dotenv { error, .. } => Some(error.as_ref()),
+            Self::ReadFile { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
 
 /// A collection of configuration errors encountered during environment variable
 /// loading.
@@ -64,6 +202,34 @@ impl StdError for FromEnvError {}
 ///   1. `Config.database_url`: Missing required environment variable 'DATABASE_URL'
 ///   2. `Config.port`: Failed to parse 'PORT'="invalid": invalid digit found in string
 /// ```
+///
+/// # Inspecting Errors
+///
+/// Each [`FromEnvError`] can be inspected individually by iterating over the
+/// collection:
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use fromenv::FromEnv;
+///
+/// #[derive(FromEnv, Debug)]
+/// pub struct Config {
+///     #[env(from = "DATABASE_URL")]
+///     database_url: String,
+/// }
+///
+/// let errors = Config::from_source(&HashMap::new()).finalize().unwrap_err();
+///
+/// for error in &errors {
+///     eprintln!(
+///         "kind={:?} path={:?} env_var={:?}",
+///         error.kind(),
+///         error.path().map(|path| path.as_str()),
+///         error.env_var(),
+///     );
+/// }
+/// ```
 #[derive(Debug, Default)]
 pub struct FromEnvErrors(Vec<FromEnvError>);
 
@@ -84,6 +250,18 @@ impl FromEnvErrors {
         !self.0.is_empty()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FromEnvError> {
+        self.0.iter()
+    }
+
     pub fn only_missing_errors(&self) -> bool {
         self.0.iter().all(|e| {
             matches!(
@@ -94,6 +272,24 @@ impl FromEnvErrors {
     }
 }
 
+impl IntoIterator for FromEnvErrors {
+    type Item = FromEnvError;
+    type IntoIter = std::vec::IntoIter<FromEnvError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a FromEnvErrors {
+    type Item = &'a FromEnvError;
+    type IntoIter = std::slice::Iter<'a, FromEnvError>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
 impl fmt::Display for FromEnvErrors {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self.0.len() {
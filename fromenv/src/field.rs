@@ -0,0 +1,464 @@
+use std::{ffi::OsString, path::PathBuf};
+
+use crate::{
+    BoxError,
+    context::Context,
+    error::{FromEnvError, FromEnvErrors},
+    parser::{self, InsertMap, Parser},
+    path::FieldPath,
+    requirement::{Format, Requirement},
+    warning::{FromEnvWarning, FromEnvWarnings},
+};
+
+/// Describes how the derive macro reads a `#[env(from)]` field.
+pub struct FlatField {
+    pub field: &'static str,
+    pub path: &'static str,
+    pub env_var: &'static str,
+    pub ty: &'static str,
+    pub parser: &'static str,
+    pub doc: Option<&'static str>,
+    pub struct_doc: Option<&'static str>,
+    pub optional: bool,
+    pub default: Option<&'static str>,
+    pub aliases: &'static [&'static str],
+    pub file_fallback: bool,
+    pub sensitive: bool,
+    pub sep: Option<&'static str>,
+    pub kv_sep: Option<&'static str>,
+    pub unit: Option<&'static str>,
+}
+
+/// Replaces the values of sensitive fields in errors, warnings and docs.
+const REDACTED: &str = "***";
+
+// Errors are recorded in `errors`, `Err(())` only signals that the field
+// couldn't be read, matching the generated `finalize`.
+#[allow(clippy::result_unit_err)]
+impl FlatField {
+    /// Reads a field that must be present, either in the environment or as a
+    /// default.
+    pub fn required<T>(
+        &self,
+        context: &Context,
+        parser: &impl Parser<T>,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<T, ()> {
+        let value = self.read(context, parser, errors, warnings)?;
+        self.require(context, value, errors)
+    }
+
+    /// Reads an `Option<T>` field, which is `None` when the variable isn't
+    /// set.
+    pub fn optional<T>(
+        &self,
+        context: &Context,
+        parser: &impl Parser<T>,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<Option<T>, ()> {
+        self.read(context, parser, errors, warnings)
+    }
+
+    /// Reads a `#[env(from, os)]` field that must be present.
+    pub fn required_os<T>(
+        &self,
+        context: &Context,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<T, ()>
+    where
+        T: From<OsString>,
+    {
+        let value = self.read_os(context, errors, warnings)?;
+        self.require(context, value, errors)
+    }
+
+    /// Reads an optional `#[env(from, os)]` field.
+    pub fn optional_os<T>(
+        &self,
+        context: &Context,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<Option<T>, ()>
+    where
+        T: From<OsString>,
+    {
+        self.read_os(context, errors, warnings)
+    }
+
+    /// Describes the variable read by the field.
+    pub fn requirement(&self, context: &Context) -> Requirement {
+        Requirement {
+            env_var: context.env_var(self.env_var),
+            path: self.path(context),
+            ty: self.ty.to_owned(),
+            default: self
+                .default
+                .map(|default| if self.sensitive { REDACTED } else { default }.to_owned()),
+            optional: self.optional,
+            sensitive: self.sensitive,
+            parser: self.parser.to_owned(),
+            doc: self.doc.map(ToOwned::to_owned),
+            struct_doc: self.struct_doc.map(ToOwned::to_owned),
+            aliases: self
+                .aliases
+                .iter()
+                .map(|alias| context.env_var(alias))
+                .collect(),
+            file_fallback: self.file_fallback,
+            format: match (self.sep, self.kv_sep) {
+                (Some(sep), Some(kv_sep)) => Some(Format::Map {
+                    sep: sep.to_owned(),
+                    kv_sep: kv_sep.to_owned(),
+                }),
+                (Some(sep), None) => Some(Format::List {
+                    sep: sep.to_owned(),
+                }),
+                (None, _) if self.parser == "fromenv::duration" => Some(Format::Duration {
+                    unit: self.unit.map(ToOwned::to_owned),
+                }),
+                (None, _) if self.parser == "fromenv::byte_size" => Some(Format::ByteSize),
+                (None, _) => None,
+            },
+            pattern: false,
+        }
+    }
+
+    fn path(&self, context: &Context) -> FieldPath {
+        context.field_path(self.field, self.path)
+    }
+
+    fn require<T>(
+        &self,
+        context: &Context,
+        value: Option<T>,
+        errors: &mut FromEnvErrors,
+    ) -> Result<T, ()> {
+        match value {
+            Some(val) => Ok(val),
+            None => {
+                errors.add(FromEnvError::MissingEnv {
+                    path: self.path(context),
+                    env_var: context.env_var(self.env_var),
+                });
+                Err(())
+            }
+        }
+    }
+
+    fn read<T>(
+        &self,
+        context: &Context,
+        parser: &impl Parser<T>,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<Option<T>, ()> {
+        let Some((env_var, value)) = self.lookup(context, errors, warnings)? else {
+            return Ok(None);
+        };
+
+        let value = value.into_string().map_err(|_| {
+            errors.add(FromEnvError::NotUnicode {
+                path: self.path(context),
+                env_var: env_var.clone(),
+            });
+        })?;
+
+        parser.parse(&value).map(Some).map_err(|error| {
+            errors.add(parse_error(
+                self.path(context),
+                env_var,
+                value,
+                error,
+                self.sensitive,
+            ));
+        })
+    }
+
+    fn read_os<T>(
+        &self,
+        context: &Context,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<Option<T>, ()>
+    where
+        T: From<OsString>,
+    {
+        Ok(self
+            .lookup(context, errors, warnings)?
+            .map(|(_, value)| T::from(value)))
+    }
+
+    /// Finds the raw value for the field, along with the name of the variable
+    /// that it was read from.
+    fn lookup(
+        &self,
+        context: &Context,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<Option<(String, OsString)>, ()> {
+        let env_var = context.env_var(self.env_var);
+
+        if let Some((name, value)) = self.lookup_aliased(context, &env_var, errors)? {
+            if name != env_var {
+                warnings.add(FromEnvWarning::DeprecatedAlias {
+                    path: self.path(context),
+                    env_var,
+                    alias: name.clone(),
+                });
+            }
+
+            if value.is_empty() {
+                warnings.add(FromEnvWarning::EmptyValue {
+                    path: self.path(context),
+                    env_var: name.clone(),
+                });
+            }
+
+            return Ok(Some((name, value)));
+        }
+
+        if self.file_fallback {
+            let file_var = format!("{env_var}_FILE");
+
+            if let Some(file) = context.source().var_os(&file_var) {
+                let file = PathBuf::from(file);
+
+                return match std::fs::read_to_string(&file) {
+                    Ok(contents) => Ok(Some((file_var, trim_newline(contents).into()))),
+                    Err(error) => {
+                        errors.add(FromEnvError::ReadFile {
+                            path: self.path(context),
+                            env_var: file_var,
+                            file,
+                            error,
+                        });
+                        Err(())
+                    }
+                };
+            }
+        }
+
+        let Some(default) = self.default else {
+            return Ok(None);
+        };
+
+        warnings.add(FromEnvWarning::DefaultApplied {
+            path: self.path(context),
+            env_var: env_var.clone(),
+            default: if self.sensitive { REDACTED } else { default }.to_owned(),
+        });
+
+        Ok(Some((env_var, default.into())))
+    }
+
+    /// Looks up the primary variable followed by each alias in turn, returning
+    /// the first value found. Any other names that are set must hold the same
+    /// value.
+    fn lookup_aliased(
+        &self,
+        context: &Context,
+        env_var: &str,
+        errors: &mut FromEnvErrors,
+    ) -> Result<Option<(String, OsString)>, ()> {
+        let names = std::iter::once(env_var.to_string())
+            .chain(self.aliases.iter().map(|alias| context.env_var(alias)));
+
+        let mut found: Option<(String, OsString)> = None;
+        let mut conflict = false;
+
+        for name in names {
+            let Some(value) = context.source().var_os(&name) else {
+                continue;
+            };
+
+            match &found {
+                None => found = Some((name, value)),
+                Some((found_name, found_value)) if *found_value != value => {
+                    errors.add(FromEnvError::AliasConflict {
+                        path: self.path(context),
+                        env_var: found_name.clone(),
+                        alias: name,
+                    });
+                    conflict = true;
+                }
+                Some(_) => {}
+            }
+        }
+
+        if conflict { Err(()) } else { Ok(found) }
+    }
+}
+
+/// Describes how the derive macro reads a `#[env(prefix_map = "...")]` field.
+pub struct PrefixMapField {
+    pub field: &'static str,
+    pub path: &'static str,
+    pub prefix: &'static str,
+    pub ty: &'static str,
+    pub parser: &'static str,
+    pub doc: Option<&'static str>,
+    pub struct_doc: Option<&'static str>,
+    pub lowercase: bool,
+    pub sensitive: bool,
+}
+
+#[allow(clippy::result_unit_err)]
+impl PrefixMapField {
+    /// Reads every variable starting with the prefix, keyed by the rest of its
+    /// name. Variables are read in order of their names, so that errors and
+    /// warnings are reported in a stable order.
+    pub fn read<M>(
+        &self,
+        context: &Context,
+        parser: &impl Parser<M::Value>,
+        errors: &mut FromEnvErrors,
+        warnings: &mut FromEnvWarnings,
+    ) -> Result<M, ()>
+    where
+        M: InsertMap,
+        M::Key: From<String>,
+    {
+        let prefix = context.env_var(self.prefix);
+
+        let mut names: Vec<_> = context
+            .source()
+            .var_names()
+            .into_iter()
+            .filter(|name| name.len() > prefix.len() && name.starts_with(&prefix))
+            .collect();
+        names.sort();
+        names.dedup();
+
+        let mut map = M::default();
+        // Keys read so far, along with the variable they were read from.
+        let mut keys: Vec<(String, String)> = Vec::new();
+        let mut failed = false;
+
+        for name in names {
+            let Some(value) = context.source().var_os(&name) else {
+                continue;
+            };
+
+            let value = match value.into_string() {
+                Ok(value) => value,
+                Err(_) => {
+                    errors.add(FromEnvError::NotUnicode {
+                        path: self.path(context),
+                        env_var: name,
+                    });
+                    failed = true;
+                    continue;
+                }
+            };
+
+            if value.is_empty() {
+                warnings.add(FromEnvWarning::EmptyValue {
+                    path: self.path(context),
+                    env_var: name.clone(),
+                });
+            }
+
+            let key = &name[prefix.len()..];
+            let key = if self.lowercase {
+                key.to_lowercase()
+            } else {
+                key.to_owned()
+            };
+
+            // Only possible when keys are lowercased.
+            if let Some((_, other)) = keys.iter().find(|(existing, _)| *existing == key) {
+                let error = format!("key `{key}` is also read from '{other}'");
+                errors.add(parse_error(
+                    self.path(context),
+                    name,
+                    value,
+                    error.into(),
+                    self.sensitive,
+                ));
+                failed = true;
+                continue;
+            }
+
+            match parser.parse(&value) {
+                Ok(parsed) => {
+                    keys.push((key.clone(), name));
+                    map.insert_new(key.into(), parsed);
+                }
+                Err(error) => {
+                    errors.add(parse_error(
+                        self.path(context),
+                        name,
+                        value,
+                        error,
+                        self.sensitive,
+                    ));
+                    failed = true;
+                }
+            }
+        }
+
+        if failed { Err(()) } else { Ok(map) }
+    }
+
+    /// Describes the variables read by the field, using the pattern
+    /// `PREFIX_*` as the variable name.
+    pub fn requirement(&self, context: &Context) -> Requirement {
+        Requirement {
+            env_var: format!("{}*", context.env_var(self.prefix)),
+            path: self.path(context),
+            ty: self.ty.to_owned(),
+            default: None,
+            optional: true,
+            sensitive: self.sensitive,
+            parser: self.parser.to_owned(),
+            doc: self.doc.map(ToOwned::to_owned),
+            struct_doc: self.struct_doc.map(ToOwned::to_owned),
+            aliases: Vec::new(),
+            file_fallback: false,
+            format: None,
+            pattern: true,
+        }
+    }
+
+    fn path(&self, context: &Context) -> FieldPath {
+        context.field_path(self.field, self.path)
+    }
+}
+
+/// Records a value that failed to parse. The values of sensitive fields are
+/// masked, along with anything the parser's error may have copied from them.
+fn parse_error(
+    path: FieldPath,
+    env_var: String,
+    value: String,
+    error: BoxError,
+    sensitive: bool,
+) -> FromEnvError {
+    let (value, error) = if sensitive {
+        (REDACTED.to_owned(), parser::redact(error))
+    } else {
+        (value, error)
+    };
+
+    FromEnvError::ParseError {
+        path,
+        env_var,
+        value,
+        error,
+    }
+}
+
+fn trim_newline(mut contents: String) -> String {
+    if contents.ends_with('\n') {
+        contents.pop();
+
+        if contents.ends_with('\r') {
+            contents.pop();
+        }
+    }
+
+    contents
+}
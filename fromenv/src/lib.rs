@@ -14,6 +14,9 @@
 //! * Opinionated error handling, parsing errors aren't silently ignored when
 //!   defaults are provided.
 //! * Documentation of configuration options using the `requirements` method.
+//! * Pluggable environment sources, so configuration can be loaded from a
+//!   `HashMap` or closure instead of the process environment.
+//! * Built-in support for loading `.env` files.
 //!
 //! ## Usage
 //!
@@ -66,6 +69,64 @@
 //! }
 //! ```
 //!
+//! #### Prefixes
+//!
+//! Environment variable names are never inferred from the struct hierarchy,
+//! but a prefix can be explicitly applied to every variable read by a nested
+//! struct. This makes it possible to use the same configuration type more than
+//! once.
+//!
+//! ```rust
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct PostgresConfig {
+//!     #[env(from = "PG_HOST", default = "localhost")]
+//!     host: String,
+//! }
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     // Reads `PG_HOST`.
+//!     #[env(nested)]
+//!     primary: PostgresConfig,
+//!
+//!     // Reads `REPLICA_PG_HOST`.
+//!     #[env(nested, prefix = "REPLICA_")]
+//!     replica: PostgresConfig,
+//! }
+//! ```
+//!
+//! Prefixes can also be supplied at runtime, for example when loading the same
+//! configuration once per tenant. Every variable, including those read by
+//! nested structs, is looked up under the prefix, and the prefixed names are
+//! used in error messages.
+//!
+//! ```rust,no_run
+//! # use fromenv::FromEnv;
+//! #
+//! # #[derive(FromEnv, Debug)]
+//! # pub struct PostgresConfig {
+//! #     #[env(from = "PG_HOST", default = "localhost")]
+//! #     host: String,
+//! # }
+//! #
+//! #[derive(FromEnv, Debug)]
+//! pub struct TenantConfig {
+//!     #[env(from = "API_URL")]
+//!     api_url: String,
+//!
+//!     #[env(nested)]
+//!     database: PostgresConfig,
+//! }
+//!
+//! // Reads `ACME_API_URL` and `ACME_PG_HOST`.
+//! let acme = TenantConfig::from_env_with_prefix("ACME_").finalize();
+//!
+//! // Documents `ACME_API_URL` and `ACME_PG_HOST`.
+//! let requirements = TenantConfig::requirements_with_prefix("ACME_");
+//! ```
+//!
 //! ### Custom parsers
 //!
 //! By default, `FromEnv` will use the types `FromStr` implementation. This can
@@ -86,14 +147,14 @@
 //!
 //! // Any function with the signature `fn<T>(&str) -> Result<T, Box<dyn StdError>>`
 //! // can be used as a custom parser.
-//! fn comma_separated(s: &str) -> ParseResult<Vec<String>> {
-//!     Ok(s.split(',').map(ToOwned::to_owned).collect())
+//! fn percentage(s: &str) -> ParseResult<f64> {
+//!     Ok(s.trim_end_matches('%').parse::<f64>()? / 100.0)
 //! }
 //!
 //! #[derive(FromEnv, Debug)]
-//! pub struct KafkaConfig {
-//!     #[env(from = "KAFKA_BOOTSTRAP_SERVERS", with = comma_separated)]
-//!     bootstrap_servers: Vec<String>,
+//! pub struct TracingConfig {
+//!     #[env(from = "TRACE_SAMPLE_RATE", with = percentage)]
+//!     sample_rate: f64,
 //! }
 //!
 //! #[derive(FromEnv, Debug)]
@@ -102,10 +163,199 @@
 //!     api_key: SecretString,
 //!
 //!     #[env(nested)]
-//!     kafka: KafkaConfig,
+//!     tracing: TracingConfig,
 //! }
 //! ```
 //!
+//! ### Durations
+//!
+//! `std::time::Duration` doesn't implement `FromStr`, so a `Duration` field
+//! can instead use the built in `#[env(from, with = fromenv::duration)]`
+//! parser. It accepts one or more whole numbers, each followed by a unit, such
+//! as `500ms`, `30s` or `1h30m`. The units are `ns`, `us` (or `µs`), `ms`,
+//! `s`, `m`, `h` and `d`.
+//!
+//! A bare number is an error, unless a unit is given for them using
+//! `#[env(from, with = fromenv::duration, unit = "ms")]`.
+//!
+//! The built in parser has to be named by its path, `fromenv::duration`. A
+//! bare `with = duration` calls whichever `duration` function is in scope, like
+//! any other custom parser. The same goes for `fromenv::byte_size` below.
+//!
+//! ```rust
+//! use std::{collections::HashMap, time::Duration};
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "REQUEST_TIMEOUT", with = fromenv::duration, default = "30s")]
+//!     request_timeout: Duration,
+//!     #[env(from = "POLL_INTERVAL", with = fromenv::duration, unit = "ms")]
+//!     poll_interval: Duration,
+//! }
+//!
+//! let source = HashMap::from([
+//!     ("REQUEST_TIMEOUT".to_owned(), "1m30s".to_owned()),
+//!     ("POLL_INTERVAL".to_owned(), "250".to_owned()),
+//! ]);
+//!
+//! let config = Config::from_source(&source).finalize().unwrap();
+//! assert_eq!(Duration::from_secs(90), config.request_timeout);
+//! assert_eq!(Duration::from_millis(250), config.poll_interval);
+//! ```
+//!
+//! ### Byte Sizes
+//!
+//! Sizes such as buffer or cache limits can use the built in
+//! `#[env(from, with = fromenv::byte_size)]` parser, which reads a whole
+//! number optionally followed by a unit. Both decimal units, `kB`, `MB`, `GB`,
+//! `TB`, `PB` and `EB`, and binary units, `KiB`, `MiB`, `GiB`, `TiB`, `PiB`
+//! and `EiB`, are accepted. Units are case-insensitive and the trailing `B` can be
+//! left out, so `512k` is 512,000 bytes and `10mi` is 10 MiB. A bare number is
+//! a number of bytes.
+//!
+//! The field can be any type that implements `TryFrom<u64>`, such as `u64` or
+//! `usize`. Sizes that don't fit in the type are reported as errors.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "MAX_BODY_SIZE", with = fromenv::byte_size, default = "10MiB")]
+//!     max_body_size: usize,
+//!     #[env(from = "CACHE_SIZE", with = fromenv::byte_size)]
+//!     cache_size: u64,
+//! }
+//!
+//! let source = HashMap::from([("CACHE_SIZE".to_owned(), "1GB".to_owned())]);
+//!
+//! let config = Config::from_source(&source).finalize().unwrap();
+//! assert_eq!(10 * 1024 * 1024, config.max_body_size);
+//! assert_eq!(1_000_000_000, config.cache_size);
+//! ```
+//!
+//! ### Delimited Lists
+//!
+//! A `Vec<T>` field can be read from a delimited list using
+//! `#[env(from, sep = ",")]`. The value is split on the separator, each
+//! element is trimmed and then parsed using `T`'s `FromStr` implementation, or
+//! the parser given by `with`. An empty value is an empty list.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "KAFKA_BOOTSTRAP_SERVERS", sep = ",")]
+//!     bootstrap_servers: Vec<String>,
+//!     #[env(from = "ALLOWED_PORTS", sep = ";")]
+//!     allowed_ports: Vec<u16>,
+//! }
+//!
+//! let source = HashMap::from([
+//!     (
+//!         "KAFKA_BOOTSTRAP_SERVERS".to_owned(),
+//!         "kafka-1:9092, kafka-2:9092".to_owned(),
+//!     ),
+//!     ("ALLOWED_PORTS".to_owned(), "80;443;http".to_owned()),
+//! ]);
+//!
+//! let errors = Config::from_source(&source).finalize().unwrap_err();
+//! assert!(errors.to_string().contains("element 2: invalid digit"));
+//! ```
+//!
+//! Errors say which element failed to parse, counting from zero:
+//!
+//! ```text
+//! `Config.allowed_ports`: Failed to parse 'ALLOWED_PORTS'="80;443;http": element 2: invalid digit found in string
+//! ```
+//!
+//! The element's index is also available from the [`ListElementError`]
+//! returned by the error's `source()`.
+//!
+//! ### Key/Value Maps
+//!
+//! A `HashMap<K, V>` or `BTreeMap<K, V>` field can be read from a list of
+//! key/value pairs by adding `kv_sep`, which separates each key from its
+//! value and must be different from `sep`. Keys are parsed using `K`'s
+//! `FromStr` implementation and values using `V`'s, or the parser given by
+//! `with`.
+//!
+//! Keys and values are percent-decoded, so `%2C` can be used for a `,` within
+//! a value and `%25` for a literal `%`. A `%` that isn't followed by two hex
+//! digits is kept as is, so `limit=50%` doesn't need encoding. A pair without
+//! the key/value separator and a key that appears more than once are both
+//! errors, reported with the index of the pair in the same way as list
+//! elements. The [`MapError`] returned by the error's `source()` says which.
+//!
+//! ```rust
+//! use std::collections::{BTreeMap, HashMap};
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "OTEL_RESOURCE_ATTRIBUTES", sep = ",", kv_sep = "=")]
+//!     resource_attributes: BTreeMap<String, String>,
+//! }
+//!
+//! let source = HashMap::from([(
+//!     "OTEL_RESOURCE_ATTRIBUTES".to_owned(),
+//!     "service.name=api,env=prod,owners=alice%2Cbob".to_owned(),
+//! )]);
+//!
+//! let config = Config::from_source(&source).finalize().unwrap();
+//! assert_eq!("alice,bob", config.resource_attributes["owners"]);
+//! ```
+//!
+//! ### Prefix Maps
+//!
+//! Sometimes the names of the variables aren't known ahead of time, such as
+//! labels or feature toggles passed as `LABEL_TEAM=payments` and
+//! `LABEL_TIER=backend`. A `HashMap<String, T>` or `BTreeMap<String, T>` field
+//! marked `#[env(prefix_map = "LABEL_")]` collects every variable starting
+//! with the prefix, keyed by the rest of its name. Adding `lowercase` converts
+//! the keys to lowercase.
+//!
+//! Each value is parsed using `T`'s `FromStr` implementation, or the parser
+//! given by `with`. The field is empty when no variables match, so it's never
+//! reported as missing.
+//!
+//! ```rust
+//! use std::collections::{BTreeMap, HashMap};
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(prefix_map = "LABEL_", lowercase)]
+//!     labels: BTreeMap<String, String>,
+//! }
+//!
+//! let source = HashMap::from([
+//!     ("LABEL_TEAM".to_owned(), "payments".to_owned()),
+//!     ("LABEL_TIER".to_owned(), "backend".to_owned()),
+//! ]);
+//!
+//! let config = Config::from_source(&source).finalize().unwrap();
+//! assert_eq!("payments", config.labels["team"]);
+//!
+//! assert_eq!(
+//!     "LABEL_*=  # BTreeMap<String, String>, optional\n",
+//!     Config::requirements()
+//! );
+//! ```
+//!
+//! Finding the matching variables requires listing them, using
+//! [`EnvSource::var_names`]. Closures can't be listed, so `prefix_map` fields
+//! read from a closure are always empty.
+//!
 //! ### Optional Fields
 //!
 //! Both "flat" and "nested" fields can be made optional. When making a field optional:
@@ -161,7 +411,7 @@
 //! }
 //!
 //! # #[allow(clippy::test_attr_in_doctest)]
-//! #[test]
+//! ##[test]
 //! fn test() {
 //!     // Override `port` and `telemetry.log_level` but read `database_url` from
 //!     // the environment.
@@ -175,6 +425,245 @@
 //! }
 //! ```
 //!
+//! ### Renaming Variables
+//!
+//! Variables can be renamed without breaking existing deployments by listing
+//! the old names as aliases. Aliases are tried in order when the primary name
+//! isn't set. Reading a value from an alias succeeds, but produces a warning
+//! that can be retrieved using `finalize_with_report`. If more than one of the
+//! names is set with different values, an error is returned.
+//!
+//! ```rust,no_run
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct KafkaConfig {
+//!     #[env(from = "KAFKA_BOOTSTRAP_SERVERS", alias = "KAFKA_BROKERS")]
+//!     bootstrap_servers: String,
+//! }
+//!
+//! let (config, warnings) = KafkaConfig::from_env().finalize_with_report().unwrap();
+//!
+//! if !warnings.is_empty() {
+//!     eprintln!("{warnings}");
+//! }
+//! ```
+//!
+//! ### Non-Unicode Values
+//!
+//! Values that aren't valid unicode are reported as errors rather than being
+//! treated as missing. Fields such as `PathBuf` or `OsString` which need to
+//! accept these values can be marked with `os`, in which case they are
+//! constructed from the variable's `OsString` value.
+//!
+//! ```rust
+//! use std::path::PathBuf;
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "DATA_DIR", os, default = "/var/lib/data")]
+//!     data_dir: PathBuf,
+//! }
+//! ```
+//!
+//! ### Secrets From Files
+//!
+//! Docker and Kubernetes secrets are commonly mounted as files, with the path
+//! to the file passed in a variable suffixed with `_FILE`, e.g.
+//! `POSTGRES_PASSWORD_FILE`.
+//!
+//! Fields marked with `file_fallback` will read the file named by
+//! `<ENV_NAME>_FILE` when `<ENV_NAME>` is not set. A single trailing newline is
+//! trimmed from the contents before they are passed to the field's parser.
+//!
+//! ```rust
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     // Read from `DB_PASSWORD`, or the file at `DB_PASSWORD_FILE`.
+//!     #[env(from = "DB_PASSWORD", file_fallback)]
+//!     db_password: String,
+//! }
+//! ```
+//!
+//! ### Sensitive Values
+//!
+//! Fields marked with `sensitive` never have their value included in errors,
+//! warnings or requirements. Parse errors show `***` in place of the value,
+//! and the parser's error message is dropped since it may quote the value.
+//! Only the position of a failing list element or map pair is kept. Fields
+//! using the `secrecy` crate's `SecretString`, `SecretBox` or `Secret` types
+//! are treated as sensitive automatically.
+//!
+//! ```rust
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     // A parse failure is reported as `Failed to parse 'API_KEY'="***"`.
+//!     #[env(from = "API_KEY", sensitive)]
+//!     api_key: String,
+//! }
+//! ```
+//!
+//! ### Environment Sources
+//!
+//! `from_env` reads variables from the process environment, but any type that
+//! implements [`EnvSource`] can be used instead by calling `from_source`.
+//! Implementations are provided for `HashMap<String, String>` and for closures
+//! of the form `Fn(&str) -> Option<String>`.
+//!
+//! Supplying the variables directly avoids mutating global state, which means
+//! that tests can safely run in parallel.
+//!
+//! ```rust
+//! use std::collections::HashMap;
+//!
+//! use fromenv::FromEnv;
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from)]
+//!     database_url: String,
+//!
+//!     #[env(from, default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! let source = HashMap::from([(
+//!     "DATABASE_URL".to_owned(),
+//!     "postgres://postgres@postgres/postgres".to_owned(),
+//! )]);
+//!
+//! let config = Config::from_source(&source).finalize().unwrap();
+//! ```
+//!
+//! ### Dotenv Files
+//!
+//! [`Dotenv`] loads variables from a `.env` file and can be used as a source.
+//! Sources can be layered using [`EnvSource::or`], which lets you choose
+//! whether the file ranks above or below the process environment.
+//!
+//! Syntax errors in the file are returned as [`FromEnvErrors`] that identify
+//! the file and line number.
+//!
+//! ```rust,no_run
+//! use fromenv::{Dotenv, EnvSource, FromEnv, ProcessEnv};
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from)]
+//!     database_url: String,
+//! }
+//!
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     // Variables set in the process environment take precedence.
+//!     let source = ProcessEnv.or(Dotenv::from_path(".env")?);
+//!
+//!     let config = Config::from_source(&source).finalize()?;
+//!
+//!     Ok(())
+//! }
+//! ```
+//!
+//! ### Documenting Requirements
+//!
+//! `requirements()` returns a `NAME=default` line for every variable read by
+//! the struct, including those of nested structs. Each line notes the field's
+//! type and whether the variable is required, optional or has a default. Doc
+//! comments on fields are included as `#` comments above the variable, and
+//! doc comments on nested structs as `##` section headers.
+//!
+//! ```rust
+//! use fromenv::FromEnv;
+//!
+//! /// Kafka connection settings.
+//! #[derive(FromEnv, Debug)]
+//! pub struct KafkaConfig {
+//!     #[env(from = "KAFKA_BROKER")]
+//!     broker: String,
+//! }
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     /// The port to listen on.
+//!     #[env(from = "PORT", default = "8080")]
+//!     port: u16,
+//!
+//!     #[env(nested)]
+//!     kafka: KafkaConfig,
+//! }
+//!
+//! println!("{}", Config::requirements());
+//! ```
+//!
+//! ```text
+//! # The port to listen on.
+//! PORT=8080  # u16, optional (default)
+//!
+//! ## Kafka connection settings.
+//! KAFKA_BROKER=  # String, required
+//! ```
+//!
+//! The same information is available in a structured form from
+//! `requirements_model()`, which returns a [`Requirement`] per variable
+//! describing its name, field path, type, default, parser and doc comments,
+//! and whether it is optional or sensitive. The [`render`] module renders
+//! requirements in other formats, including [`render::env_compat`] for the
+//! earlier format of bare `NAME=default` lines.
+//!
+//! [`render::markdown`] renders Markdown tables suitable for a README, with a
+//! subsection for each nested struct.
+//!
+//! `json_schema()` returns a JSON Schema describing the environment as an
+//! object of string properties, so that deployment manifests can be validated
+//! without compiling the service.
+//!
+//! [`render::kubernetes_env`] and [`render::compose_environment`] render the
+//! `env:` list of a Kubernetes container and the `environment:` block of a
+//! docker-compose service, so that deployments can be generated from the same
+//! definition.
+//!
+//! A variable that is read by more than one field, for example by nested
+//! structs from different crates, is only documented once when every field
+//! reads it in the same way. [`assert_consistent`] can be used in a test to
+//! check that no two fields disagree on how a variable is read, such as its
+//! type, parser, default or whether it's sensitive.
+//!
+//! [`RequirementsDiff`] compares two sets of requirements and classifies each
+//! change by its [`Severity`]. For example, a new required variable is
+//! breaking, while a new optional variable is compatible. Release tooling can
+//! use this to reject a minor version that introduces a new mandatory
+//! variable. With the `serde` feature enabled, a [`Requirement`] can be
+//! serialized, so the requirements of the last release can be kept as a
+//! snapshot, e.g. in JSON, and read back to compare against.
+//!
+//! [`render::dotenv_example`] renders a commented template for a
+//! `.env.example` file. Combined with [`render::write_if_changed`] in a test,
+//! this keeps the file in sync with the code:
+//!
+//! ```rust,no_run
+//! # use fromenv::FromEnv;
+//! #
+//! # #[derive(FromEnv, Debug)]
+//! # pub struct Config {
+//! #     #[env(from = "PORT", default = "8080")]
+//! #     port: u16,
+//! # }
+//! #
+//! # #[allow(clippy::test_attr_in_doctest)]
+//! #[test]
+//! fn dotenv_example_is_up_to_date() {
+//!     let example = fromenv::render::dotenv_example(&Config::requirements_model());
+//!     let changed = fromenv::render::write_if_changed(".env.example", &example).unwrap();
+//!
+//!     assert!(!changed, ".env.example was out of date and has been regenerated");
+//! }
+//! ```
+//!
 //! ## Attribute Options
 //!
 //! * `#[env(from = "ENV_NAME")]` - Load from specified environment variable.
@@ -183,7 +672,28 @@
 //! * `#[env(from, default = "value")]` - Default value if environment variable
 //!   is not set.
 //! * `#[env(from, with = parser_fn)]` - Custom parser function.
+//! * `#[env(from, with = fromenv::duration)]` - Parse a `Duration` such as
+//!   `30s` or `1h30m`, add `unit = "ms"` to accept bare numbers in that unit.
+//! * `#[env(from, with = fromenv::byte_size)]` - Parse a number of bytes such
+//!   as `512k`, `10MiB` or `1GB`.
+//! * `#[env(from, alias = "OLD_NAME")]` - Fall back to a deprecated variable
+//!   name, can be repeated to try several aliases in order.
+//! * `#[env(from, file_fallback)]` - Read the value from the file named by
+//!   `<ENV_NAME>_FILE` if the environment variable is not set.
+//! * `#[env(from, os)]` - Construct the field from the variable's `OsString`
+//!   value using `From<OsString>`, allowing values which aren't valid unicode.
+//! * `#[env(from, sensitive)]` - Mask the value in errors, warnings and
+//!   requirements.
+//! * `#[env(from, sep = ",")]` - Parse a `Vec<T>` from a list separated by
+//!   `,`, parsing each element with `T`'s `FromStr` or the `with` parser.
+//! * `#[env(from, sep = ",", kv_sep = "=")]` - Parse a `HashMap<K, V>` or
+//!   `BTreeMap<K, V>` from `KEY=VALUE` pairs separated by `,`.
+//! * `#[env(prefix_map = "PREFIX_")]` - Collect every variable starting with
+//!   `PREFIX_` into a map keyed by the rest of the name, can be combined with
+//!   `with`, `sensitive` and `lowercase`.
 //! * `#[env(nested)]` - For nested configuration structures.
+//! * `#[env(nested, prefix = "PREFIX_")]` - For nested configuration structures
+//!   whose environment variables should all be prefixed with `PREFIX_`.
 //! * It is possible skip the `env` attribute for a field, but to avoid any
 //!   errors the value must be set using the override methods before calling
 //!   `finalize`.
@@ -200,14 +710,57 @@
 //!     1. `Config.database_url`: Missing required environment variable 'DATABASE_URL'
 //!     2. `Config.port`: Failed to parse 'PORT'="invalid": invalid digit found in string
 //! ```
+//!
+//! Each error is identified by the full path to the field from the root
+//! struct, so a field in a nested struct is reported as
+//! `AppConfig.kafka.broker`. This distinguishes between the same nested type
+//! used in more than one place. The struct-local form, `KafkaConfig.broker`,
+//! is available from [`FieldPath::local`].
+//!
+//! Individual errors can be inspected by iterating over `FromEnvErrors`. Each
+//! [`FromEnvError`] exposes its [`kind`](FromEnvError::kind),
+//! [`path`](FromEnvError::path), [`env_var`](FromEnvError::env_var) and
+//! [`value`](FromEnvError::value), which is useful for structured logging or
+//! metrics. For parse errors, [`source`](std::error::Error::source) returns
+//! the error produced by the parser.
+//!
+//! ## Warnings
+//!
+//! Some issues aren't serious enough to prevent the configuration from
+//! loading, but are still worth knowing about. The `finalize_with_report()`
+//! method returns the configuration together with [`FromEnvWarnings`], which
+//! records:
+//!
+//! * Values read from deprecated aliases.
+//! * Defaults that were applied because a variable wasn't set.
+//! * Variables that were set to an empty string.
+//! * Optional nested configuration that was dropped because it was
+//!   incomplete.
+//!
+//! ```text
+//! 2 configuration warnings:
+//!   1. `Config.port`: 'PORT' is not set, using default "8080"
+//!   2. `Config.kafka`: Optional configuration was not loaded because it is incomplete
+//! ```
+mod context;
+mod diff;
+mod dotenv;
 mod error;
+mod field;
 mod parser;
+mod path;
+pub mod render;
+mod requirement;
+mod source;
+mod warning;
 
 #[doc(hidden)]
 #[path = "private.rs"]
 pub mod __private;
 
-pub use error::FromEnvErrors;
+pub use diff::{Change, ChangeKind, RequirementsDiff, Severity};
+pub use dotenv::Dotenv;
+pub use error::{FromEnvError, FromEnvErrorKind, FromEnvErrors};
 
 /// Derive macro for loading configuration from environment variables.
 ///
@@ -217,7 +770,28 @@ pub use error::FromEnvErrors;
 /// * `#[env(from)]` - Load from environment variable matching field's uppercase name.
 /// * `#[env(from, default = "value")]` - Default value if environment variable is not set.
 /// * `#[env(from, with = parser_fn)]` - Custom parser function.
+/// * `#[env(from, with = fromenv::duration)]` - Parse a `Duration` such as
+///   `30s` or `1h30m`, add `unit = "ms"` to accept bare numbers in that unit.
+/// * `#[env(from, with = fromenv::byte_size)]` - Parse a number of bytes such
+///   as `512k`, `10MiB` or `1GB`.
+/// * `#[env(from, alias = "OLD_NAME")]` - Fall back to a deprecated variable
+///   name, can be repeated to try several aliases in order.
+/// * `#[env(from, file_fallback)]` - Read the value from the file named by
+///   `<ENV_NAME>_FILE` if the environment variable is not set.
+/// * `#[env(from, os)]` - Construct the field from the variable's `OsString`
+///   value using `From<OsString>`, allowing values which aren't valid unicode.
+/// * `#[env(from, sensitive)]` - Mask the value in errors, warnings and
+///   requirements.
+/// * `#[env(from, sep = ",")]` - Parse a `Vec<T>` from a list separated by
+///   `,`, parsing each element with `T`'s `FromStr` or the `with` parser.
+/// * `#[env(from, sep = ",", kv_sep = "=")]` - Parse a `HashMap<K, V>` or
+///   `BTreeMap<K, V>` from `KEY=VALUE` pairs separated by `,`.
+/// * `#[env(prefix_map = "PREFIX_")]` - Collect every variable starting with
+///   `PREFIX_` into a map keyed by the rest of the name, can be combined with
+///   `with`, `sensitive` and `lowercase`.
 /// * `#[env(nested)]` - For nested configuration structures.
+/// * `#[env(nested, prefix = "PREFIX_")]` - For nested configuration structures
+///   whose environment variables should all be prefixed with `PREFIX_`.
 /// * It is possible skip the `env` attribute for a field, but to avoid any
 ///   errors the value must be set using the override methods before calling
 ///   `finalize`.
@@ -249,6 +823,10 @@ pub use error::FromEnvErrors;
 /// # }
 /// ```
 pub use fromenv_derive::FromEnv;
-pub use parser::ParseResult;
+pub use parser::{ListElementError, MapError, ParseResult, byte_size, duration};
+pub use path::FieldPath;
+pub use requirement::{Format, Requirement, assert_consistent};
+pub use source::{EnvSource, Layered, ProcessEnv};
+pub use warning::{FromEnvWarning, FromEnvWarnings};
 
 type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
@@ -1,3 +1,12 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error as StdError,
+    fmt,
+    hash::{BuildHasher, Hash},
+    str::FromStr,
+    time::Duration,
+};
+
 use crate::BoxError;
 
 /// Return type for functions that can be used with the `with` attribute.
@@ -5,13 +14,6 @@ pub type ParseResult<T> = Result<T, BoxError>;
 
 pub trait Parser<T> {
     fn parse(&self, s: &str) -> ParseResult<T>;
-
-    fn parse_from_env(&self, env_var: &str) -> Option<(String, Result<T, BoxError>)> {
-        std::env::var(env_var).ok().map(|value| {
-            let parse_result = self.parse(&value);
-            (value, parse_result)
-        })
-    }
 }
 
 pub fn from_str<T>(s: &str) -> ParseResult<T>
@@ -29,6 +31,180 @@ where
     Ok(s.to_owned().into())
 }
 
+/// The units accepted by [`duration`], along with their length in
+/// nanoseconds.
+pub(crate) const DURATION_UNITS: &[(&str, u64)] = &[
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60 * 1_000_000_000),
+    ("h", 60 * 60 * 1_000_000_000),
+    ("d", 24 * 60 * 60 * 1_000_000_000),
+];
+
+/// Parses a duration such as `500ms`, `30s` or `1h30m`, made up of one or
+/// more whole numbers each followed by a unit.
+///
+/// This is the parser used by `#[env(from, with = fromenv::duration)]` fields.
+pub fn duration(s: &str) -> ParseResult<Duration> {
+    parse_duration(s, None)
+}
+
+/// Parses a duration using [`duration`], except that a bare whole number is
+/// read in `unit`, for `#[env(from, with = fromenv::duration, unit = "...")]`
+/// fields.
+pub struct DurationWithUnit(pub &'static str);
+
+impl Parser<Duration> for DurationWithUnit {
+    fn parse(&self, s: &str) -> ParseResult<Duration> {
+        parse_duration(s, Some(self.0))
+    }
+}
+
+fn parse_duration(s: &str, default_unit: Option<&str>) -> ParseResult<Duration> {
+    let invalid = |problem: &str| -> BoxError {
+        let units: Vec<_> = DURATION_UNITS.iter().map(|(unit, _)| *unit).collect();
+        format!(
+            "{problem}, expected a duration such as `30s` or `1h30m` using the units {}",
+            units.join(", ")
+        )
+        .into()
+    };
+
+    let s = s.trim();
+    if s.is_empty() {
+        return Err(invalid("empty duration"));
+    }
+
+    if let Some(unit) = default_unit {
+        if s.bytes().all(|b| b.is_ascii_digit()) {
+            return parse_duration(&format!("{s}{unit}"), None);
+        }
+    }
+
+    let mut nanos: u128 = 0;
+    let mut rest = s;
+
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            return Err(invalid("expected a number"));
+        }
+        let (number, tail) = rest.split_at(digits);
+
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        if unit.is_empty() {
+            return Err(invalid(&format!("missing unit after `{number}`")));
+        }
+
+        let Some((_, scale)) = DURATION_UNITS.iter().find(|(name, _)| *name == unit) else {
+            return Err(invalid(&format!("unknown unit `{unit}`")));
+        };
+
+        let number: u128 = number
+            .parse()
+            .map_err(|_| invalid("duration is too large"))?;
+        nanos = number
+            .checked_mul(u128::from(*scale))
+            .and_then(|component| nanos.checked_add(component))
+            .ok_or_else(|| invalid("duration is too large"))?;
+
+        rest = tail;
+    }
+
+    let secs =
+        u64::try_from(nanos / 1_000_000_000).map_err(|_| invalid("duration is too large"))?;
+    // The remainder is always less than a second.
+    let subsec_nanos = (nanos % 1_000_000_000) as u32;
+
+    Ok(Duration::new(secs, subsec_nanos))
+}
+
+/// The units accepted by [`byte_size`], along with their size in bytes. Units
+/// are matched case-insensitively, and the trailing `B` may be omitted from
+/// all but `B` itself.
+pub(crate) const BYTE_SIZE_UNITS: &[(&str, u64)] = &[
+    ("B", 1),
+    ("kB", 1_000),
+    ("MB", 1_000_000),
+    ("GB", 1_000_000_000),
+    ("TB", 1_000_000_000_000),
+    ("PB", 1_000_000_000_000_000),
+    ("EB", 1_000_000_000_000_000_000),
+    ("KiB", 1 << 10),
+    ("MiB", 1 << 20),
+    ("GiB", 1 << 30),
+    ("TiB", 1 << 40),
+    ("PiB", 1 << 50),
+    ("EiB", 1 << 60),
+];
+
+/// Parses a number of bytes such as `512`, `512k`, `10MiB` or `1GB`, made up
+/// of a whole number optionally followed by a decimal (`kB`, `MB`, ...) or
+/// binary (`KiB`, `MiB`, ...) unit.
+///
+/// This is the parser used by `#[env(from, with = fromenv::byte_size)]`
+/// fields.
+pub fn byte_size<T>(s: &str) -> ParseResult<T>
+where
+    T: TryFrom<u64>,
+{
+    let invalid = |problem: String| -> BoxError {
+        let units: Vec<_> = BYTE_SIZE_UNITS.iter().map(|(unit, _)| *unit).collect();
+        format!(
+            "{problem}, expected a byte size such as `512k`, `10MiB` or `1GB` using the units {}",
+            units.join(", ")
+        )
+        .into()
+    };
+
+    let s = s.trim();
+    let digits = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (number, unit) = s.split_at(digits);
+    let unit = unit.trim_start();
+
+    if number.is_empty() {
+        return Err(invalid("missing number".to_owned()));
+    }
+
+    // A bare number is a number of bytes.
+    let scale = if unit.is_empty() {
+        1
+    } else {
+        let Some((_, scale)) = BYTE_SIZE_UNITS.iter().find(|(name, _)| {
+            unit.eq_ignore_ascii_case(name)
+                || (*name != "B" && unit.eq_ignore_ascii_case(&name[..name.len() - 1]))
+        }) else {
+            return Err(invalid(format!("unknown unit `{unit}`")));
+        };
+        *scale
+    };
+
+    let too_large =
+        || -> BoxError { format!("`{s}` is too large, the maximum is {} bytes", u64::MAX).into() };
+
+    let bytes = number
+        .parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(scale))
+        .ok_or_else(too_large)?;
+
+    T::try_from(bytes).map_err(|_| {
+        format!(
+            "`{s}` is {bytes} bytes, which is too large for `{}`",
+            std::any::type_name::<T>()
+        )
+        .into()
+    })
+}
+
 impl<T, F> Parser<T> for F
 where
     F: for<'a> Fn(&'a str) -> ParseResult<T>,
@@ -37,3 +213,297 @@ where
         (self)(s)
     }
 }
+
+/// Parses a delimited list, using `parser` for each element.
+pub struct List<P> {
+    sep: &'static str,
+    parser: P,
+}
+
+impl<P> List<P> {
+    pub fn new(sep: &'static str, parser: P) -> Self {
+        Self { sep, parser }
+    }
+}
+
+impl<T, P> Parser<Vec<T>> for List<P>
+where
+    P: Parser<T>,
+{
+    fn parse(&self, s: &str) -> ParseResult<Vec<T>> {
+        if s.trim().is_empty() {
+            return Ok(Vec::new());
+        }
+
+        s.split(self.sep)
+            .enumerate()
+            .map(|(index, element)| {
+                self.parser
+                    .parse(element.trim())
+                    .map_err(|error| ListElementError { index, error }.into())
+            })
+            .collect()
+    }
+}
+
+/// The error returned when an element of a `#[env(from, sep = "...")]` list
+/// fails to parse.
+///
+/// The element itself isn't included, so that the error can't reveal part of a
+/// sensitive value.
+#[derive(Debug)]
+pub struct ListElementError {
+    index: usize,
+    error: BoxError,
+}
+
+impl ListElementError {
+    /// The zero based index of the element that failed to parse.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl fmt::Display for ListElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "element {}: {}", self.index, self.error)
+    }
+}
+
+impl StdError for ListElementError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        Some(self.error.as_ref())
+    }
+}
+
+/// Parses a map of key/value pairs, using `FromStr` for each key and `parser`
+/// for each value.
+pub struct Map<P> {
+    sep: &'static str,
+    kv_sep: &'static str,
+    parser: P,
+}
+
+impl<P> Map<P> {
+    pub fn new(sep: &'static str, kv_sep: &'static str, parser: P) -> Self {
+        Self {
+            sep,
+            kv_sep,
+            parser,
+        }
+    }
+}
+
+/// Maps that can be read by a `#[env(from, sep = "...", kv_sep = "...")]`
+/// field.
+pub trait InsertMap: Default {
+    type Key;
+    type Value;
+
+    /// Inserts the pair, returning `false` if the key was already present.
+    fn insert_new(&mut self, key: Self::Key, value: Self::Value) -> bool;
+}
+
+impl<K, V, S> InsertMap for HashMap<K, V, S>
+where
+    K: Eq + Hash,
+    S: BuildHasher + Default,
+{
+    type Key = K;
+    type Value = V;
+
+    fn insert_new(&mut self, key: K, value: V) -> bool {
+        self.insert(key, value).is_none()
+    }
+}
+
+impl<K, V> InsertMap for BTreeMap<K, V>
+where
+    K: Ord,
+{
+    type Key = K;
+    type Value = V;
+
+    fn insert_new(&mut self, key: K, value: V) -> bool {
+        self.insert(key, value).is_none()
+    }
+}
+
+impl<M, P> Parser<M> for Map<P>
+where
+    M: InsertMap,
+    M::Key: FromStr,
+    <M::Key as FromStr>::Err: StdError + Send + Sync + 'static,
+    P: Parser<M::Value>,
+{
+    fn parse(&self, s: &str) -> ParseResult<M> {
+        let mut map = M::default();
+
+        if s.trim().is_empty() {
+            return Ok(map);
+        }
+
+        for (index, pair) in s.split(self.sep).enumerate() {
+            let Some((key, value)) = pair.split_once(self.kv_sep) else {
+                return Err(MapError::MalformedPair {
+                    index,
+                    kv_sep: self.kv_sep,
+                }
+                .into());
+            };
+
+            let key = percent_decode(key.trim()).ok_or(MapError::InvalidEncoding { index })?;
+            let value = percent_decode(value.trim()).ok_or(MapError::InvalidEncoding { index })?;
+
+            let key = key
+                .parse::<M::Key>()
+                .map_err(|error| MapError::InvalidKey {
+                    index,
+                    error: error.into(),
+                })?;
+            let value = self
+                .parser
+                .parse(&value)
+                .map_err(|error| MapError::InvalidValue { index, error })?;
+
+            if !map.insert_new(key, value) {
+                return Err(MapError::DuplicateKey { index }.into());
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+/// Decodes `%XX` sequences, so that keys and values can contain the
+/// separators. A `%` that isn't followed by two hex digits is kept as is, so
+/// values such as `50%` don't need encoding. Returns `None` if the decoded
+/// bytes aren't UTF-8.
+fn percent_decode(s: &str) -> Option<String> {
+    if !s.contains('%') {
+        return Some(s.to_owned());
+    }
+
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+
+    while let Some((&byte, tail)) = rest.split_first() {
+        if let Some(decoded) = (byte == b'%').then(|| decode_hex(tail)).flatten() {
+            bytes.push(decoded);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+
+    String::from_utf8(bytes).ok()
+}
+
+fn decode_hex(s: &[u8]) -> Option<u8> {
+    let hex = s.get(..2)?;
+    if !hex.iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+
+    u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok()
+}
+
+/// The error returned when a pair of a `#[env(from, sep = "...", kv_sep =
+/// "...")]` map fails to parse.
+///
+/// As with [`ListElementError`], the pair itself isn't included.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MapError {
+    /// The pair doesn't contain the key/value separator.
+    MalformedPair { index: usize, kv_sep: &'static str },
+    /// The key or value contains percent-encoded bytes that aren't UTF-8.
+    InvalidEncoding { index: usize },
+    /// The key appears in an earlier pair.
+    DuplicateKey { index: usize },
+    /// The key failed to parse.
+    InvalidKey { index: usize, error: BoxError },
+    /// The value failed to parse.
+    InvalidValue { index: usize, error: BoxError },
+}
+
+impl MapError {
+    /// The zero based index of the pair that failed to parse.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::MalformedPair { index, .. }
+            | Self::InvalidEncoding { index }
+            | Self::DuplicateKey { index }
+            | Self::InvalidKey { index, .. }
+            | Self::InvalidValue { index, .. } => *index,
+        }
+    }
+}
+
+impl fmt::Display for MapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "pair {}: ", self.index())?;
+
+        match self {
+            Self::MalformedPair { kv_sep, .. } => write!(f, "expected `KEY{kv_sep}VALUE`"),
+            Self::InvalidEncoding { .. } => f.write_str("invalid percent-encoding"),
+            Self::DuplicateKey { .. } => f.write_str("duplicate key"),
+            Self::InvalidKey { error, .. } => write!(f, "invalid key: {error}"),
+            Self::InvalidValue { error, .. } => write!(f, "invalid value: {error}"),
+        }
+    }
+}
+
+impl StdError for MapError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Self::InvalidKey { error, .. } | Self::InvalidValue { error, .. } => {
+                Some(error.as_ref())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Replaces a parser error for a sensitive value with one that can't reveal
+/// any part of it. Only the position of a failing list element or map pair is
+/// kept, as parsers are free to quote their input.
+pub(crate) fn redact(error: BoxError) -> BoxError {
+    let error = match error.downcast::<ListElementError>() {
+        Ok(error) => {
+            let ListElementError { index, error } = *error;
+            return Box::new(ListElementError {
+                index,
+                error: redact(error),
+            });
+        }
+        Err(error) => error,
+    };
+
+    match error.downcast::<MapError>() {
+        Ok(error) => Box::new(match *error {
+            MapError::InvalidKey { index, error } => MapError::InvalidKey {
+                index,
+                error: redact(error),
+            },
+            MapError::InvalidValue { index, error } => MapError::InvalidValue {
+                index,
+                error: redact(error),
+            },
+            error => error,
+        }),
+        Err(_) => Box::new(Redacted),
+    }
+}
+
+#[derive(Debug)]
+struct Redacted;
+
+impl fmt::Display for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("details redacted")
+    }
+}
+
+impl StdError for Redacted {}
@@ -0,0 +1,51 @@
+use std::fmt;
+
+/// The path to a field from the root configuration struct, e.g.
+/// `AppConfig.kafka.broker`.
+///
+/// The struct-local form of the path, e.g. `KafkaConfig.broker`, is also
+/// available using [`FieldPath::local`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FieldPath {
+    full: String,
+    local: String,
+}
+
+impl FieldPath {
+    /// Creates a path from its full and struct-local forms.
+    pub fn new(full: impl Into<String>, local: impl Into<String>) -> Self {
+        Self {
+            full: full.into(),
+            local: local.into(),
+        }
+    }
+
+    /// The full path from the root configuration struct.
+    pub fn as_str(&self) -> &str {
+        &self.full
+    }
+
+    /// The path relative to the struct that declares the field.
+    pub fn local(&self) -> &str {
+        &self.local
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.full)
+    }
+}
+
+impl PartialEq<str> for FieldPath {
+    fn eq(&self, other: &str) -> bool {
+        self.full == other
+    }
+}
+
+impl PartialEq<&str> for FieldPath {
+    fn eq(&self, other: &&str) -> bool {
+        self.full == *other
+    }
+}
@@ -6,19 +6,34 @@
 //! `Ty as Trait` syntax we can both assert that the nested struct has derived
 //! `FromEnv` and gain access to its associated builder type without having to
 //! name it explicitly.
+pub use crate::context::Context;
 pub use crate::error::{FromEnvError, FromEnvErrors};
-pub use crate::parser::{Parser, from_str, into};
+pub use crate::field::{FlatField, PrefixMapField};
+pub use crate::parser::{
+    DurationWithUnit, InsertMap, List, Map, Parser, byte_size, duration, from_str, into,
+};
+pub use crate::render::{env as render_env, json_schema as render_json_schema};
+pub use crate::requirement::Requirement;
+pub use crate::source::{EnvSource, ProcessEnv};
+pub use crate::warning::{FromEnvWarning, FromEnvWarnings};
 
 pub trait FromEnv {
-    type FromEnvBuilder: FromEnvBuilder;
+    type FromEnvBuilder<'a>: FromEnvBuilder<Target = Self>;
 
-    fn from_env() -> Self::FromEnvBuilder;
+    /// The name of the struct, used as the root of field paths.
+    const NAME: &'static str;
 
-    fn requirements(requirements: &mut String);
+    fn from_context(context: Context<'_>) -> Self::FromEnvBuilder<'_>;
+
+    fn requirements(context: &Context<'_>, requirements: &mut Vec<Requirement>);
 }
 
-pub trait FromEnvBuilder {
+pub trait FromEnvBuilder: Sized {
     type Target;
 
-    fn finalize(self) -> Result<Self::Target, FromEnvErrors>;
+    fn finalize_with_report(self) -> Result<(Self::Target, FromEnvWarnings), FromEnvErrors>;
+
+    fn finalize(self) -> Result<Self::Target, FromEnvErrors> {
+        self.finalize_with_report().map(|(target, _)| target)
+    }
 }
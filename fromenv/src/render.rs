@@ -0,0 +1,647 @@
+//! Renderers for the requirements returned by `Config::requirements_model()`.
+//!
+//! When several fields read the same variable in the same way, for example
+//! because a nested struct is used twice without a prefix, the variable is
+//! only rendered once.
+//!
+//! ```rust
+//! use fromenv::{FromEnv, render};
+//!
+//! #[derive(FromEnv, Debug)]
+//! pub struct Config {
+//!     #[env(from = "PORT", default = "8080")]
+//!     port: u16,
+//! }
+//!
+//! let requirements = Config::requirements_model();
+//!
+//! assert_eq!("PORT=8080  # u16, optional (default)\n", render::env(&requirements));
+//! assert_eq!("PORT=8080\n", render::env_compat(&requirements));
+//! ```
+mod json;
+
+use std::{fmt::Write, io, path::Path};
+
+use crate::{
+    parser::DURATION_UNITS,
+    path::FieldPath,
+    requirement::{Format, Requirement, collapse},
+};
+
+use self::json::Json;
+
+/// Renders requirements as `NAME=default` lines, with a trailing comment
+/// giving the field's type and whether the variable is required, optional or
+/// has a default, e.g. `PORT=8080  # u16, optional (default)`.
+///
+/// Field doc comments are written as `#` comments above the variable, and the
+/// doc comments of nested structs as `##` section headers.
+///
+/// This is the format returned by `Config::requirements()`.
+pub fn env(requirements: &[Requirement]) -> String {
+    write_env(requirements)
+}
+
+/// Renders requirements in the format used by earlier versions of
+/// `Config::requirements()`, with a bare `NAME=default` line per variable and
+/// no comments.
+pub fn env_compat(requirements: &[Requirement]) -> String {
+    let mut output = String::new();
+
+    for requirement in collapse(requirements) {
+        let env_var = &requirement.env_var;
+        let default = requirement.default.as_deref().unwrap_or_default();
+
+        let _ = writeln!(output, "{env_var}={default}");
+    }
+
+    output
+}
+
+/// Renders requirements as a commented `.env` template, suitable for a
+/// `.env.example` file.
+///
+/// Required variables are left uncommented with an empty value for the user to
+/// fill in, while variables that are optional or have a default are commented
+/// out. Each variable is preceded by its doc comment and a line giving its
+/// type, and the doc comments of nested structs are written as `##` section
+/// headers.
+///
+/// ```text
+/// # The port to listen on.
+/// # u16, optional (default)
+/// # PORT=8080
+///
+/// # String, required
+/// DATABASE_URL=
+/// ```
+pub fn dotenv_example(requirements: &[Requirement]) -> String {
+    let mut output = String::new();
+    let mut section = None;
+
+    for requirement in collapse(requirements) {
+        let header = section_change(&mut section, requirement);
+
+        if !output.is_empty() {
+            output.push('\n');
+        }
+        if let Some(Some(header)) = header {
+            for line in header.lines() {
+                write_comment(&mut output, "##", line);
+            }
+            output.push('\n');
+        }
+
+        for line in requirement.doc.iter().flat_map(|doc| doc.lines()) {
+            write_comment(&mut output, "#", line);
+        }
+        write_comment(&mut output, "#", &notes(requirement).join(", "));
+
+        let env_var = &requirement.env_var;
+        let default = requirement.default.as_deref().unwrap_or_default();
+
+        if requirement.is_required() {
+            let _ = writeln!(output, "{env_var}=");
+        } else {
+            let _ = writeln!(output, "# {env_var}={default}");
+        }
+    }
+
+    output
+}
+
+/// Renders requirements as Markdown tables, with columns for the variable
+/// name, type, default, whether it's required, whether it's sensitive and its
+/// description.
+///
+/// Each nested struct gets its own table under a `###` heading named after
+/// the field, followed by the struct's doc comment.
+///
+/// ```text
+/// | Variable | Type | Default | Required | Sensitive | Description |
+/// | --- | --- | --- | --- | --- | --- |
+/// | `PORT` | `u16` | `8080` | optional | no | The port to listen on. |
+///
+/// ### `kafka`
+///
+/// Kafka connection settings.
+///
+/// | Variable | Type | Default | Required | Sensitive | Description |
+/// | --- | --- | --- | --- | --- | --- |
+/// | `KAFKA_BROKER` | `String` |  | required | no |  |
+/// ```
+pub fn markdown(requirements: &[Requirement]) -> String {
+    let mut output = String::new();
+    let mut section = None;
+
+    for requirement in collapse(requirements) {
+        let parent = parent_path(&requirement.path);
+
+        if section != Some(parent) {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+
+            match parent.split_once('.') {
+                Some((_, name)) => {
+                    let _ = writeln!(output, "### `{name}`\n");
+
+                    if let Some(doc) = &requirement.struct_doc {
+                        let _ = writeln!(output, "{doc}\n");
+                    }
+                }
+                // Fields of the root struct only need a heading when they
+                // follow a nested struct.
+                None if section.is_some() => {
+                    let _ = writeln!(output, "### `{parent}`\n");
+                }
+                None => {}
+            }
+
+            output.push_str("| Variable | Type | Default | Required | Sensitive | Description |\n");
+            output.push_str("| --- | --- | --- | --- | --- | --- |\n");
+
+            section = Some(parent);
+        }
+
+        let default = requirement
+            .default
+            .as_deref()
+            .map(|default| format!("`{}`", escape_cell(default)))
+            .unwrap_or_default();
+        let required = if requirement.is_required() {
+            "required"
+        } else {
+            "optional"
+        };
+        let sensitive = if requirement.sensitive { "yes" } else { "no" };
+
+        let mut description: Vec<String> = requirement
+            .doc
+            .iter()
+            .flat_map(|doc| doc.split("\n\n"))
+            .map(|paragraph| paragraph.split_whitespace().collect::<Vec<_>>().join(" "))
+            .collect();
+        match &requirement.format {
+            Some(Format::List { sep }) => {
+                description.push(format!("A list separated by `{}`.", escape_cell(sep)));
+            }
+            Some(Format::Map { sep, kv_sep }) => description.push(format!(
+                "A map of `KEY{}VALUE` pairs separated by `{}`.",
+                escape_cell(kv_sep),
+                escape_cell(sep)
+            )),
+            Some(Format::Duration { unit }) => {
+                description.push("A duration such as `30s` or `1h30m`.".to_owned());
+                if let Some(unit) = unit {
+                    description.push(format!("Bare numbers are read in `{unit}`."));
+                }
+            }
+            Some(Format::ByteSize) => {
+                description.push("A byte size such as `512k`, `10MiB` or `1GB`.".to_owned());
+            }
+            None => {}
+        }
+        if requirement.file_fallback {
+            description.push(format!(
+                "Can also be read from the file named by `{}_FILE`.",
+                requirement.env_var
+            ));
+        }
+        if !requirement.aliases.is_empty() {
+            let aliases: Vec<_> = requirement
+                .aliases
+                .iter()
+                .map(|alias| format!("`{alias}`"))
+                .collect();
+            description.push(format!("Deprecated aliases: {}.", aliases.join(", ")));
+        }
+
+        let _ = writeln!(
+            output,
+            "| `{}` | `{}` | {default} | {required} | {sensitive} | {} |",
+            requirement.env_var,
+            escape_cell(&requirement.ty),
+            escape_cell(&description.join("<br><br>")),
+        );
+    }
+
+    output
+}
+
+/// Renders requirements as a JSON Schema describing the environment as an
+/// object with a string property for each variable.
+///
+/// The schema records which variables are required, their defaults and
+/// descriptions, and, for types parsed with `FromStr` such as integers and
+/// `bool`, a `pattern` or `enum` constraining their values. Variables that can
+/// be read from a file or a deprecated alias are included as properties, with
+/// the requirement satisfied by any one of the names. The variables read by a
+/// `prefix_map` field are described by a `patternProperties` entry.
+///
+/// This is the format returned by `Config::json_schema()`.
+pub fn json_schema(requirements: &[Requirement]) -> String {
+    let mut properties = Json::object();
+    let mut pattern_properties = Json::object();
+    let mut required = Vec::new();
+    let mut alternatives = Vec::new();
+
+    for requirement in collapse(requirements) {
+        let env_var = &requirement.env_var;
+
+        if requirement.pattern {
+            let prefix = env_var.strip_suffix('*').unwrap_or(env_var);
+            pattern_properties.insert(
+                format!("^{}", escape_regex(prefix)),
+                property_schema(requirement),
+            );
+            continue;
+        }
+        properties.insert(env_var, property_schema(requirement));
+
+        let mut names = vec![env_var.clone()];
+
+        if requirement.file_fallback {
+            let file_var = format!("{env_var}_FILE");
+
+            let mut file = Json::object();
+            file.insert("type", Json::string("string"));
+            file.insert(
+                "description",
+                Json::string(format!("Path to a file containing the value of {env_var}.")),
+            );
+            properties.insert(&file_var, file);
+            names.push(file_var);
+        }
+
+        for alias in &requirement.aliases {
+            let mut deprecated = property_schema(requirement);
+            deprecated.insert(
+                "description",
+                Json::string(format!("Deprecated alias for {env_var}.")),
+            );
+            deprecated.insert("deprecated", Json::Bool(true));
+            properties.insert(alias, deprecated);
+            names.push(alias.clone());
+        }
+
+        if !requirement.is_required() {
+            continue;
+        }
+
+        if names.len() == 1 {
+            required.push(env_var.clone());
+        } else {
+            let any_of = names
+                .into_iter()
+                .map(|name| {
+                    let mut schema = Json::object();
+                    schema.insert("required", Json::strings([name]));
+                    schema
+                })
+                .collect();
+
+            let mut schema = Json::object();
+            schema.insert("anyOf", Json::Array(any_of));
+            alternatives.push(schema);
+        }
+    }
+
+    let mut schema = Json::object();
+    schema.insert(
+        "$schema",
+        Json::string("https://json-schema.org/draft/2020-12/schema"),
+    );
+    if let Some((root, _)) = requirements
+        .first()
+        .and_then(|requirement| requirement.path.as_str().split_once('.'))
+    {
+        schema.insert("title", Json::string(root));
+    }
+    schema.insert("type", Json::string("object"));
+    schema.insert("properties", properties);
+    if !pattern_properties.is_empty() {
+        schema.insert("patternProperties", pattern_properties);
+    }
+    schema.insert("required", Json::strings(required));
+    if !alternatives.is_empty() {
+        schema.insert("allOf", Json::Array(alternatives));
+    }
+
+    format!("{schema}\n")
+}
+
+fn property_schema(requirement: &Requirement) -> Json {
+    let mut schema = Json::object();
+    schema.insert("type", Json::string("string"));
+
+    if let Some(doc) = &requirement.doc {
+        schema.insert("description", Json::string(doc));
+    }
+
+    // The default of a sensitive field is masked, so it isn't a valid value.
+    if let Some(default) = requirement
+        .default
+        .as_ref()
+        .filter(|_| !requirement.sensitive)
+    {
+        schema.insert("default", Json::string(default));
+    }
+
+    if requirement.parser == "from_str" {
+        match requirement.value_type() {
+            "bool" => schema.insert("enum", Json::strings(["true", "false"])),
+            "u8" | "u16" | "u32" | "u64" | "u128" | "usize" => {
+                schema.insert("pattern", Json::string("^\\+?[0-9]+$"))
+            }
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" => {
+                schema.insert("pattern", Json::string("^[+-]?[0-9]+$"))
+            }
+            _ => {}
+        }
+    }
+
+    match &requirement.format {
+        Some(Format::Duration { unit }) => {
+            let units: Vec<_> = DURATION_UNITS.iter().map(|(unit, _)| *unit).collect();
+            let component = format!("[0-9]+({})", units.join("|"));
+            let pattern = match unit {
+                Some(_) => format!("^(({component})+|[0-9]+)$"),
+                None => format!("^({component})+$"),
+            };
+            schema.insert("pattern", Json::string(pattern));
+        }
+        Some(Format::ByteSize) => {
+            // Units are case-insensitive, and the trailing `B` is optional.
+            schema.insert(
+                "pattern",
+                Json::string("^[0-9]+ *([kKmMgGtTpPeE][iI]?[bB]?|[bB])?$"),
+            );
+        }
+        _ => {}
+    }
+
+    if requirement.sensitive {
+        schema.insert("writeOnly", Json::Bool(true));
+    }
+
+    schema
+}
+
+/// Renders requirements as the `env:` list of a Kubernetes container.
+///
+/// Sensitive variables are read from the Secret named `secret_name` using a
+/// `secretKeyRef` keyed by the variable's name. Other variables are given
+/// their default. Variables without a default, and the patterns matched by
+/// `prefix_map` fields, are commented out. Required variables are marked as
+/// such, and still fail to load until they're filled in and uncommented.
+///
+/// ```text
+/// env:
+///   - name: PORT
+///     value: "8080"
+///   # - name: DATABASE_URL
+///   #   value: ""  # required
+///   - name: API_KEY
+///     valueFrom:
+///       secretKeyRef:
+///         name: "my-app"
+///         key: API_KEY
+/// ```
+pub fn kubernetes_env(requirements: &[Requirement], secret_name: &str) -> String {
+    let mut output = String::from("env:\n");
+    let secret_name = yaml_string(secret_name);
+
+    for requirement in collapse(requirements) {
+        let name = &requirement.env_var;
+
+        if requirement.pattern {
+            // A pattern isn't a valid name, so it's only given as a hint.
+            let _ = writeln!(output, "  # - name: {name}");
+        } else if requirement.sensitive {
+            let _ = writeln!(output, "  - name: {name}");
+            let _ = writeln!(output, "    valueFrom:");
+            let _ = writeln!(output, "      secretKeyRef:");
+            let _ = writeln!(output, "        name: {secret_name}");
+            let _ = writeln!(output, "        key: {name}");
+            if !requirement.is_required() {
+                let _ = writeln!(output, "        optional: true");
+            }
+        } else if let Some(default) = &requirement.default {
+            let _ = writeln!(output, "  - name: {name}");
+            let _ = writeln!(output, "    value: {}", yaml_string(default));
+        } else if requirement.is_required() {
+            // An empty value would read as a set variable, so a forgotten
+            // variable is only caught if it's left out.
+            let _ = writeln!(output, "  # - name: {name}");
+            let _ = writeln!(output, "  #   value: \"\"  # required");
+        } else {
+            let _ = writeln!(output, "  # - name: {name}");
+            let _ = writeln!(output, "  #   value: \"\"");
+        }
+    }
+
+    output
+}
+
+/// Renders requirements as the `environment:` block of a docker-compose
+/// service.
+///
+/// Each variable is interpolated from the environment that `docker compose`
+/// runs in, falling back to its default. Required variables use `:?` so that
+/// compose refuses to start when they're missing. Optional variables without
+/// a default, and sensitive variables with a default, are commented out, as
+/// are variables whose default contains a `}` that would end the
+/// interpolation early. The field's own default still applies to those.
+///
+/// ```text
+/// environment:
+///   PORT: "${PORT:-8080}"
+///   DATABASE_URL: "${DATABASE_URL:?DATABASE_URL is required}"
+/// ```
+pub fn compose_environment(requirements: &[Requirement]) -> String {
+    let mut output = String::from("environment:\n");
+
+    for requirement in collapse(requirements) {
+        let env_var = &requirement.env_var;
+
+        let value = match &requirement.default {
+            _ if requirement.is_required() => {
+                format!("${{{env_var}:?{env_var} is required}}")
+            }
+            // The default of a sensitive field is masked, so it can't be used.
+            Some(default) if !requirement.sensitive && !default.contains('}') => {
+                let default = default.replace('$', "$$");
+                format!("${{{env_var}:-{default}}}")
+            }
+            _ => {
+                let _ = writeln!(output, "  # {env_var}: \"${{{env_var}}}\"");
+                continue;
+            }
+        };
+
+        let _ = writeln!(output, "  {env_var}: {}", yaml_string(&value));
+    }
+
+    output
+}
+
+/// Writes `contents` to the file at `path`, unless it already holds exactly
+/// those contents. Returns whether the file was written.
+///
+/// This can be used from a test to keep a generated file such as
+/// `.env.example` in sync with the code. The test regenerates the file when
+/// run locally, and fails in CI if the committed file is out of date:
+///
+/// ```rust,no_run
+/// use fromenv::{FromEnv, render};
+///
+/// #[derive(FromEnv, Debug)]
+/// pub struct Config {
+///     #[env(from = "DATABASE_URL")]
+///     database_url: String,
+/// }
+///
+/// let example = render::dotenv_example(&Config::requirements_model());
+/// let changed = render::write_if_changed(".env.example", &example).unwrap();
+///
+/// assert!(!changed, ".env.example was out of date and has been regenerated");
+/// ```
+pub fn write_if_changed(path: impl AsRef<Path>, contents: &str) -> io::Result<bool> {
+    let path = path.as_ref();
+
+    match std::fs::read_to_string(path) {
+        Ok(existing) if existing == contents => Ok(false),
+        Ok(_) => std::fs::write(path, contents).map(|()| true),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => {
+            std::fs::write(path, contents).map(|()| true)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+fn write_env(requirements: &[Requirement]) -> String {
+    let mut output = String::new();
+    let mut section = None;
+
+    for requirement in collapse(requirements) {
+        if let Some(header) = section_change(&mut section, requirement) {
+            if !output.is_empty() {
+                output.push('\n');
+            }
+            for line in header.into_iter().flat_map(str::lines) {
+                write_comment(&mut output, "##", line);
+            }
+        }
+
+        for line in requirement.doc.iter().flat_map(|doc| doc.lines()) {
+            write_comment(&mut output, "#", line);
+        }
+
+        let env_var = &requirement.env_var;
+        let default = requirement.default.as_deref().unwrap_or_default();
+        let notes = notes(requirement).join(", ");
+
+        let _ = writeln!(output, "{env_var}={default}  # {notes}");
+    }
+
+    output
+}
+
+/// The struct that declared the previous requirement, along with its header.
+type Section<'a> = Option<(&'a str, Option<&'a str>)>;
+
+/// Tracks the nested struct that each requirement belongs to. Returns
+/// `Some(header)` when moving into a struct with a different section header,
+/// where `header` is the doc comment of the new struct.
+fn section_change<'a>(
+    section: &mut Section<'a>,
+    requirement: &'a Requirement,
+) -> Option<Option<&'a str>> {
+    let parent = parent_path(&requirement.path);
+    // The root struct's doc comment isn't used as a header.
+    let header = if parent.contains('.') {
+        requirement.struct_doc.as_deref()
+    } else {
+        None
+    };
+
+    if section.is_some_and(|(path, _)| path == parent) {
+        return None;
+    }
+
+    let previous = section.and_then(|(_, header)| header);
+    *section = Some((parent, header));
+
+    (header.is_some() || previous.is_some()).then_some(header)
+}
+
+/// The notes describing a requirement: its type, whether it's required, and
+/// any format, sensitivity, file fallback and aliases.
+fn notes(requirement: &Requirement) -> Vec<String> {
+    let env_var = &requirement.env_var;
+
+    let mut notes = vec![requirement.ty.clone(), status(requirement).to_owned()];
+    if let Some(format) = &requirement.format {
+        notes.push(format.to_string());
+    }
+    if requirement.sensitive {
+        notes.push("sensitive".to_owned());
+    }
+    if requirement.file_fallback {
+        notes.push(format!("or {env_var}_FILE"));
+    }
+    if !requirement.aliases.is_empty() {
+        notes.push(format!("aliases: {}", requirement.aliases.join(", ")));
+    }
+    notes
+}
+
+fn status(requirement: &Requirement) -> &'static str {
+    if requirement.default.is_some() {
+        "optional (default)"
+    } else if requirement.optional {
+        "optional"
+    } else {
+        "required"
+    }
+}
+
+/// The path to the struct that declares the field, e.g. `AppConfig.kafka` for
+/// `AppConfig.kafka.broker`.
+fn parent_path(path: &FieldPath) -> &str {
+    path.as_str()
+        .rsplit_once('.')
+        .map_or(path.as_str(), |(parent, _)| parent)
+}
+
+/// Escapes the characters that have a special meaning in a regular
+/// expression.
+fn escape_regex(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if "\\^$.|?*+()[]{}".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Quotes a YAML scalar, JSON strings are valid YAML.
+fn yaml_string(value: &str) -> String {
+    Json::string(value).to_string()
+}
+
+/// Escapes characters that would end a Markdown table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+fn write_comment(output: &mut String, marker: &str, line: &str) {
+    if line.is_empty() {
+        let _ = writeln!(output, "{marker}");
+    } else {
+        let _ = writeln!(output, "{marker} {line}");
+    }
+}
@@ -0,0 +1,106 @@
+use std::fmt::{self, Write};
+
+/// A minimal JSON value, used to render JSON Schemas without depending on
+/// `serde_json`.
+pub(super) enum Json {
+    Bool(bool),
+    String(String),
+    Array(Vec<Json>),
+    /// Keys are written in insertion order.
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub(super) fn object() -> Self {
+        Self::Object(Vec::new())
+    }
+
+    pub(super) fn string(value: impl Into<String>) -> Self {
+        Self::String(value.into())
+    }
+
+    pub(super) fn strings<S: Into<String>>(values: impl IntoIterator<Item = S>) -> Self {
+        Self::Array(values.into_iter().map(Self::string).collect())
+    }
+
+    /// Sets a key of an object, replacing any existing value. Panics if `self`
+    /// isn't an object.
+    pub(super) fn insert(&mut self, key: impl Into<String>, value: Json) {
+        let Self::Object(entries) = self else {
+            panic!("expected a JSON object");
+        };
+
+        let key = key.into();
+        match entries.iter_mut().find(|(existing, _)| *existing == key) {
+            Some((_, existing)) => *existing = value,
+            None => entries.push((key, value)),
+        }
+    }
+
+    /// Whether `self` is an empty array or object.
+    pub(super) fn is_empty(&self) -> bool {
+        match self {
+            Self::Array(values) => values.is_empty(),
+            Self::Object(entries) => entries.is_empty(),
+            Self::Bool(_) | Self::String(_) => false,
+        }
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{value}"),
+            Self::String(value) => write_string(f, value),
+            Self::Array(values) if values.is_empty() => f.write_str("[]"),
+            Self::Array(values) => {
+                f.write_str("[\n")?;
+                for (i, value) in values.iter().enumerate() {
+                    write_indent(f, indent + 1)?;
+                    value.write(f, indent + 1)?;
+                    f.write_str(if i + 1 < values.len() { ",\n" } else { "\n" })?;
+                }
+                write_indent(f, indent)?;
+                f.write_char(']')
+            }
+            Self::Object(entries) if entries.is_empty() => f.write_str("{}"),
+            Self::Object(entries) => {
+                f.write_str("{\n")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    write_indent(f, indent + 1)?;
+                    write_string(f, key)?;
+                    f.write_str(": ")?;
+                    value.write(f, indent + 1)?;
+                    f.write_str(if i + 1 < entries.len() { ",\n" } else { "\n" })?;
+                }
+                write_indent(f, indent)?;
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Pretty prints the value with two space indentation.
+impl fmt::Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+fn write_indent(f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+    write!(f, "{:width$}", "", width = indent * 2)
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for c in value.chars() {
+        match c {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => f.write_char(c)?,
+        }
+    }
+    f.write_char('"')
+}
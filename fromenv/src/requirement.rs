@@ -0,0 +1,326 @@
+use std::fmt::{self, Write};
+
+use crate::path::FieldPath;
+
+/// Describes an environment variable read by a configuration struct.
+///
+/// The requirements for a struct are returned by `Config::requirements_model()`
+/// and include every variable read by the struct and its nested structs, in
+/// field order. They can be used to generate documentation or validate
+/// deployment manifests.
+///
+/// With the `serde` feature enabled, requirements can be serialized and read
+/// back, so that a snapshot taken at a release can later be compared against
+/// using [`RequirementsDiff::between`](crate::RequirementsDiff::between).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Requirement {
+    /// The name of the variable, including any prefix.
+    pub env_var: String,
+    /// The path to the field from the root struct. The struct that declares
+    /// the field can be found from [`FieldPath::local`].
+    pub path: FieldPath,
+    /// The Rust type of the field as written in the struct, e.g.
+    /// `Option<u16>`.
+    pub ty: String,
+    /// The default value, which is masked for sensitive fields.
+    pub default: Option<String>,
+    /// Whether the variable can be left unset because the field is an
+    /// `Option`, or belongs to an optional nested struct.
+    pub optional: bool,
+    /// Whether the field is marked `sensitive`.
+    pub sensitive: bool,
+    /// The parser used for the value, e.g. `from_str`, `into`, the path to a
+    /// custom `with` function, or `os` for fields read as an `OsString`.
+    pub parser: String,
+    /// The field's doc comment.
+    pub doc: Option<String>,
+    /// The doc comment of the struct that declares the field.
+    pub struct_doc: Option<String>,
+    /// Deprecated names for the variable, including any prefix.
+    pub aliases: Vec<String>,
+    /// Whether the value can be read from the file named by `<ENV_VAR>_FILE`.
+    pub file_fallback: bool,
+    /// The format of the value, for fields that aren't parsed from the whole
+    /// value, such as delimited lists, maps, durations and byte sizes.
+    pub format: Option<Format>,
+    /// Whether `env_var` is a pattern such as `LABEL_*`, matching every
+    /// variable read by a `#[env(prefix_map = "LABEL_")]` field.
+    pub pattern: bool,
+}
+
+/// The format of a variable's value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum Format {
+    /// A list of elements separated by `sep`, read by a
+    /// `#[env(from, sep = "...")]` field.
+    List { sep: String },
+    /// A map of `KEY<kv_sep>VALUE` pairs separated by `sep`, read by a
+    /// `#[env(from, sep = "...", kv_sep = "...")]` field.
+    Map { sep: String, kv_sep: String },
+    /// A duration such as `30s` or `1h30m`, read by a
+    /// `#[env(from, with = fromenv::duration)]` field. Bare numbers are read in
+    /// `unit`, if one is given.
+    Duration { unit: Option<String> },
+    /// A number of bytes such as `512k`, `10MiB` or `1GB`, read by a
+    /// `#[env(from, with = fromenv::byte_size)]` field.
+    ByteSize,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::List { sep } => write!(f, "list separated by \"{sep}\""),
+            Self::Map { sep, kv_sep } => {
+                write!(f, "map of KEY{kv_sep}VALUE pairs separated by \"{sep}\"")
+            }
+            Self::Duration { unit: None } => f.write_str("duration such as 30s or 1h30m"),
+            Self::Duration { unit: Some(unit) } => {
+                write!(f, "duration such as 30s or 1h30m, bare numbers in {unit}")
+            }
+            Self::ByteSize => f.write_str("byte size such as 512k, 10MiB or 1GB"),
+        }
+    }
+}
+
+impl Requirement {
+    /// Whether the variable must be set for the configuration to load, i.e.
+    /// it isn't optional and has no default.
+    pub fn is_required(&self) -> bool {
+        !self.optional && self.default.is_none()
+    }
+
+    /// The name of the type that's parsed from the variable, without any
+    /// `Option` or module path, e.g. `Duration` for
+    /// `Option<std::time::Duration>`.
+    pub(crate) fn value_type(&self) -> &str {
+        let ty = self
+            .ty
+            .strip_prefix("Option<")
+            .and_then(|inner| inner.strip_suffix('>'))
+            .unwrap_or(&self.ty);
+
+        ty.rsplit("::").next().unwrap_or(ty)
+    }
+
+    /// Whether `other` reads the same variable in the same way, regardless of
+    /// which field it belongs to.
+    fn same_definition(&self, other: &Self) -> bool {
+        self.env_var == other.env_var
+            && self.ty == other.ty
+            && self.default == other.default
+            && self.optional == other.optional
+            && self.sensitive == other.sensitive
+            && self.parser == other.parser
+            && self.aliases == other.aliases
+            && self.file_fallback == other.file_fallback
+            && self.format == other.format
+            && self.pattern == other.pattern
+    }
+}
+
+/// Drops requirements which duplicate an earlier requirement, so that a
+/// variable read the same way by several nested structs is only documented
+/// once.
+pub(crate) fn collapse(requirements: &[Requirement]) -> Vec<&Requirement> {
+    let mut collapsed: Vec<&Requirement> = Vec::new();
+
+    for requirement in requirements {
+        if !collapsed
+            .iter()
+            .any(|existing| existing.same_definition(requirement))
+        {
+            collapsed.push(requirement);
+        }
+    }
+
+    collapsed
+}
+
+/// Asserts that every field in `requirements`, as returned by
+/// `Config::requirements_model()`, that reads the same variable agrees on how
+/// it's read: its type, parser, format, default, aliases, file fallback and
+/// whether it's optional or sensitive.
+///
+/// This is intended to be used in a test, to catch configuration composed
+/// from several crates that read the same variable in different ways.
+///
+/// ```rust,should_panic
+/// use fromenv::FromEnv;
+///
+/// #[derive(FromEnv)]
+/// pub struct DatabaseConfig {
+///     #[env(from = "DATABASE_URL")]
+///     url: String,
+/// }
+///
+/// #[derive(FromEnv)]
+/// pub struct CacheConfig {
+///     #[env(from = "DATABASE_URL", default = "postgres://localhost/cache")]
+///     url: String,
+/// }
+///
+/// #[derive(FromEnv)]
+/// pub struct AppConfig {
+///     #[env(nested)]
+///     database: DatabaseConfig,
+///     #[env(nested)]
+///     cache: CacheConfig,
+/// }
+///
+/// fromenv::assert_consistent(&AppConfig::requirements_model());
+/// ```
+///
+/// # Panics
+///
+/// Panics with a description of each conflicting variable if any conflicts
+/// are found.
+#[track_caller]
+pub fn assert_consistent(requirements: &[Requirement]) {
+    let mut message = String::new();
+
+    for (i, first) in requirements.iter().enumerate() {
+        // Only report each variable once, from its first use.
+        if requirements[..i]
+            .iter()
+            .any(|earlier| earlier.env_var == first.env_var)
+        {
+            continue;
+        }
+
+        for other in requirements[i + 1..]
+            .iter()
+            .filter(|other| other.env_var == first.env_var)
+        {
+            let mut differences = Vec::new();
+            if first.ty != other.ty {
+                differences.push(format!("type `{}` vs `{}`", first.ty, other.ty));
+            }
+            if first.parser != other.parser {
+                differences.push(format!("parser `{}` vs `{}`", first.parser, other.parser));
+            }
+            if first.default != other.default {
+                differences.push(format!(
+                    "default {} vs {}",
+                    describe_default(first),
+                    describe_default(other)
+                ));
+            }
+            if first.format != other.format {
+                differences.push(format!(
+                    "format {} vs {}",
+                    describe_format(first),
+                    describe_format(other)
+                ));
+            }
+            if first.optional != other.optional {
+                differences.push(format!(
+                    "{} vs {}",
+                    describe_optional(first),
+                    describe_optional(other)
+                ));
+            }
+            if first.sensitive != other.sensitive {
+                differences.push(format!(
+                    "{} vs {}",
+                    describe_sensitive(first),
+                    describe_sensitive(other)
+                ));
+            }
+            if first.aliases != other.aliases {
+                differences.push(format!(
+                    "aliases {} vs {}",
+                    describe_aliases(first),
+                    describe_aliases(other)
+                ));
+            }
+            if first.file_fallback != other.file_fallback {
+                differences.push(format!(
+                    "{} vs {}",
+                    describe_file_fallback(first),
+                    describe_file_fallback(other)
+                ));
+            }
+            if first.pattern != other.pattern {
+                differences.push(format!(
+                    "{} vs {}",
+                    describe_pattern(first),
+                    describe_pattern(other)
+                ));
+            }
+
+            if !differences.is_empty() {
+                let _ = writeln!(
+                    message,
+                    "  '{}' is read by `{}` and `{}`: {}",
+                    first.env_var,
+                    first.path,
+                    other.path,
+                    differences.join(", ")
+                );
+            }
+        }
+    }
+
+    if !message.is_empty() {
+        panic!("conflicting environment variables:\n{message}");
+    }
+}
+
+fn describe_default(requirement: &Requirement) -> String {
+    match &requirement.default {
+        Some(default) => format!("\"{default}\""),
+        None => "none".to_owned(),
+    }
+}
+
+fn describe_format(requirement: &Requirement) -> String {
+    match &requirement.format {
+        Some(format) => format.to_string(),
+        None => "none".to_owned(),
+    }
+}
+
+fn describe_optional(requirement: &Requirement) -> &'static str {
+    if requirement.optional {
+        "optional"
+    } else {
+        "not optional"
+    }
+}
+
+fn describe_sensitive(requirement: &Requirement) -> &'static str {
+    if requirement.sensitive {
+        "sensitive"
+    } else {
+        "not sensitive"
+    }
+}
+
+fn describe_aliases(requirement: &Requirement) -> String {
+    if requirement.aliases.is_empty() {
+        "none".to_owned()
+    } else {
+        requirement.aliases.join(", ")
+    }
+}
+
+fn describe_file_fallback(requirement: &Requirement) -> &'static str {
+    if requirement.file_fallback {
+        "file fallback"
+    } else {
+        "no file fallback"
+    }
+}
+
+fn describe_pattern(requirement: &Requirement) -> &'static str {
+    if requirement.pattern {
+        "prefix map"
+    } else {
+        "single variable"
+    }
+}
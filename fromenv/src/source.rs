@@ -0,0 +1,142 @@
+use std::{collections::HashMap, ffi::OsString, hash::BuildHasher};
+
+/// A source of environment variables.
+///
+/// The generated builders look up every variable through an `EnvSource`.
+/// `Config::from_env()` uses [`ProcessEnv`], while `Config::from_source(&source)`
+/// lets you supply any other implementation, which is useful for running tests
+/// in parallel without mutating the process environment.
+///
+/// Implementations are provided for:
+///
+/// * [`ProcessEnv`], the environment of the current process.
+/// * `HashMap<String, String>`.
+/// * Closures of the form `Fn(&str) -> Option<String>`.
+/// * [`Dotenv`](crate::Dotenv), variables loaded from a `.env` file.
+///
+/// ```rust
+/// use std::collections::HashMap;
+///
+/// use fromenv::FromEnv;
+///
+/// #[derive(FromEnv, Debug)]
+/// pub struct Config {
+///     #[env(from = "PORT")]
+///     port: u16,
+/// }
+///
+/// let source = HashMap::from([("PORT".to_owned(), "8080".to_owned())]);
+/// let config = Config::from_source(&source).finalize().unwrap();
+///
+/// let config = Config::from_source(&|key: &str| (key == "PORT").then(|| "8080".to_owned()))
+///     .finalize()
+///     .unwrap();
+/// ```
+pub trait EnvSource {
+    /// Returns the value of the variable `key`, or `None` if it isn't set.
+    fn var(&self, key: &str) -> Option<String>;
+
+    /// Returns the value of the variable `key` without requiring it to be
+    /// valid unicode, or `None` if it isn't set.
+    ///
+    /// Sources that can hold values which aren't valid unicode should override
+    /// this method, so that such values are reported as errors rather than
+    /// being treated as missing.
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.var(key).map(OsString::from)
+    }
+
+    /// Returns the names of the variables in the source, which is used to
+    /// find the variables read by `#[env(prefix_map = "...")]` fields.
+    ///
+    /// Sources that can't list their variables, such as closures, return no
+    /// names, so `prefix_map` fields read from them are always empty.
+    fn var_names(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Layers this source above `fallback`, which is only consulted for
+    /// variables that aren't set in `self`.
+    fn or<S>(self, fallback: S) -> Layered<Self, S>
+    where
+        Self: Sized,
+        S: EnvSource,
+    {
+        Layered {
+            first: self,
+            second: fallback,
+        }
+    }
+}
+
+/// Two sources layered on top of each other, created using [`EnvSource::or`].
+#[derive(Debug, Clone)]
+pub struct Layered<A, B> {
+    first: A,
+    second: B,
+}
+
+impl<A, B> EnvSource for Layered<A, B>
+where
+    A: EnvSource,
+    B: EnvSource,
+{
+    fn var(&self, key: &str) -> Option<String> {
+        self.first.var(key).or_else(|| self.second.var(key))
+    }
+
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        self.first.var_os(key).or_else(|| self.second.var_os(key))
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        let mut names = self.first.var_names();
+        names.extend(self.second.var_names());
+        names.sort();
+        names.dedup();
+        names
+    }
+}
+
+/// The environment of the current process, read using [`std::env::var_os`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProcessEnv;
+
+impl EnvSource for ProcessEnv {
+    fn var(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+
+    fn var_os(&self, key: &str) -> Option<OsString> {
+        std::env::var_os(key)
+    }
+
+    /// Variables whose names aren't valid unicode are skipped.
+    fn var_names(&self) -> Vec<String> {
+        std::env::vars_os()
+            .filter_map(|(name, _)| name.into_string().ok())
+            .collect()
+    }
+}
+
+impl<S> EnvSource for HashMap<String, String, S>
+where
+    S: BuildHasher,
+{
+    fn var(&self, key: &str) -> Option<String> {
+        self.get(key).cloned()
+    }
+
+    fn var_names(&self) -> Vec<String> {
+        self.keys().cloned().collect()
+    }
+}
+
+impl<F> EnvSource for F
+where
+    F: Fn(&str) -> Option<String>,
+{
+    fn var(&self, key: &str) -> Option<String> {
+        (self)(key)
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::path::FieldPath;
+
+/// A non-fatal issue encountered while loading configuration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FromEnvWarning {
+    /// The value was read from a deprecated alias rather than the primary
+    /// variable name.
+    DeprecatedAlias {
+        path: FieldPath,
+        env_var: String,
+        alias: String,
+    },
+    /// The variable wasn't set, so the field's default was used.
+    DefaultApplied {
+        path: FieldPath,
+        env_var: String,
+        default: String,
+    },
+    /// The variable was set to an empty string.
+    EmptyValue { path: FieldPath, env_var: String },
+    /// An optional nested struct was set to `None` because some of its
+    /// variables weren't set.
+    OptionalGroupDropped { path: FieldPath },
+}
+
+impl fmt::Display for FromEnvWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DeprecatedAlias {
+                path,
+                env_var,
+                alias,
+            } => {
+                write!(
+                    f,
+                    "`{path}`: Using deprecated environment variable '{alias}', use '{env_var}' instead"
+                )
+            }
+            Self::DefaultApplied {
+                path,
+                env_var,
+                default,
+            } => {
+                write!(
+                    f,
+                    "`{path}`: '{env_var}' is not set, using default \"{default}\""
+                )
+            }
+            Self::EmptyValue { path, env_var } => {
+                write!(f, "`{path}`: '{env_var}' is set to an empty string")
+            }
+            Self::OptionalGroupDropped { path } => {
+                write!(
+                    f,
+                    "`{path}`: Optional configuration was not loaded because it is incomplete"
+                )
+            }
+        }
+    }
+}
+
+/// A collection of warnings encountered while loading configuration.
+///
+/// Warnings are returned alongside the configuration by
+/// `finalize_with_report()` and don't prevent the configuration from loading.
+/// They record things that are worth knowing about at startup, such as:
+///
+/// * Values read from deprecated aliases.
+/// * Defaults that were applied because a variable wasn't set.
+/// * Variables that were set to an empty string.
+/// * Optional nested configuration that was dropped because it was
+///   incomplete.
+///
+/// # Display Format
+///
+/// When displayed, warnings are shown as a count and list of all issues:
+///
+/// ```text
+/// 1 configuration warning:
+///   1. `Config.brokers`: Using deprecated environment variable 'KAFKA_BROKERS', use 'KAFKA_BOOTSTRAP_SERVERS' instead
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FromEnvWarnings(Vec<FromEnvWarning>);
+
+impl FromEnvWarnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add(&mut self, warning: FromEnvWarning) {
+        self.0.push(warning);
+    }
+
+    pub fn extend(&mut self, other: FromEnvWarnings) {
+        self.0.extend(other.0);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, FromEnvWarning> {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for FromEnvWarnings {
+    type Item = FromEnvWarning;
+    type IntoIter = std::vec::IntoIter<FromEnvWarning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a FromEnvWarnings {
+    type Item = &'a FromEnvWarning;
+    type IntoIter = std::slice::Iter<'a, FromEnvWarning>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl fmt::Display for FromEnvWarnings {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.len() {
+            1 => writeln!(f, "1 configuration warning:")?,
+            n => writeln!(f, "{n} configuration warnings:")?,
+        }
+
+        for (i, warning) in self.0.iter().enumerate() {
+            writeln!(f, "  {}. {}", i + 1, warning)?;
+        }
+        Ok(())
+    }
+}
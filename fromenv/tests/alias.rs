@@ -0,0 +1,116 @@
+use fromenv::{FieldPath, FromEnv, FromEnvWarning};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct KafkaConfig {
+    #[env(
+        from = "KAFKA_BOOTSTRAP_SERVERS",
+        alias = "KAFKA_BROKERS",
+        alias = "BROKERS"
+    )]
+    bootstrap_servers: String,
+}
+
+#[test]
+fn primary_name_is_preferred() {
+    let source = source(&[("KAFKA_BOOTSTRAP_SERVERS", "kafka:9092")]);
+
+    let (config, warnings) = KafkaConfig::from_source(&source)
+        .finalize_with_report()
+        .unwrap();
+
+    assert_eq!(
+        KafkaConfig {
+            bootstrap_servers: "kafka:9092".into()
+        },
+        config
+    );
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn aliases_are_tried_in_order_with_a_warning() {
+    let source = source(&[("BROKERS", "b:9092")]);
+
+    let (config, warnings) = KafkaConfig::from_source(&source)
+        .finalize_with_report()
+        .unwrap();
+
+    assert_eq!(
+        KafkaConfig {
+            bootstrap_servers: "b:9092".into()
+        },
+        config
+    );
+    assert_eq!(
+        vec![FromEnvWarning::DeprecatedAlias {
+            path: FieldPath::new(
+                "KafkaConfig.bootstrap_servers",
+                "KafkaConfig.bootstrap_servers"
+            ),
+            env_var: "KAFKA_BOOTSTRAP_SERVERS".into(),
+            alias: "BROKERS".into(),
+        }],
+        warnings.into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn warning_display() {
+    let source = source(&[("KAFKA_BROKERS", "kafka:9092")]);
+
+    let expected = r#"1 configuration warning:
+  1. `KafkaConfig.bootstrap_servers`: Using deprecated environment variable 'KAFKA_BROKERS', use 'KAFKA_BOOTSTRAP_SERVERS' instead
+"#;
+    let (_, warnings) = KafkaConfig::from_source(&source)
+        .finalize_with_report()
+        .unwrap();
+
+    assert_eq!(expected, warnings.to_string());
+}
+
+#[test]
+fn same_values_are_not_a_conflict() {
+    let source = source(&[
+        ("KAFKA_BOOTSTRAP_SERVERS", "kafka:9092"),
+        ("KAFKA_BROKERS", "kafka:9092"),
+    ]);
+
+    let actual = KafkaConfig::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        KafkaConfig {
+            bootstrap_servers: "kafka:9092".into()
+        },
+        actual
+    );
+}
+
+#[test]
+fn different_values_are_a_conflict() {
+    let source = source(&[
+        ("KAFKA_BOOTSTRAP_SERVERS", "kafka:9092"),
+        ("KAFKA_BROKERS", "legacy:9092"),
+    ]);
+
+    let expected = r#"1 configuration error:
+  1. `KafkaConfig.bootstrap_servers`: 'KAFKA_BOOTSTRAP_SERVERS' and 'KAFKA_BROKERS' are both set but have different values
+"#;
+    let actual = KafkaConfig::from_source(&source)
+        .finalize()
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn requirements_list_aliases() {
+    let expected =
+        "KAFKA_BOOTSTRAP_SERVERS=  # String, required, aliases: KAFKA_BROKERS, BROKERS\n";
+
+    assert_eq!(expected, KafkaConfig::requirements());
+}
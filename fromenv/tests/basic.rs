@@ -135,6 +135,13 @@ fn requirements() {
     }
 
     let actual = Config::requirements();
+    let expected = "\
+        OTEL_RESOURCE_ATTRIBUTES=  # Option<String>, optional\n\
+        OTEL_LOG_LEVEL=info  # String, optional (default)\n\
+    ";
+    assert_eq!(expected, actual);
+
+    let actual = fromenv::render::env_compat(&Config::requirements_model());
     let expected = "\
         OTEL_RESOURCE_ATTRIBUTES=\n\
         OTEL_LOG_LEVEL=info\n\
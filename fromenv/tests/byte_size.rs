@@ -0,0 +1,107 @@
+use fromenv::{Format, FromEnv};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from = "MAX_BODY_SIZE", with = fromenv::byte_size, default = "10MiB")]
+    max_body_size: usize,
+    #[env(from = "CACHE_SIZE", with = fromenv::byte_size)]
+    cache_size: Option<u64>,
+    #[env(from = "BUFFER_SIZE", with = fromenv::byte_size)]
+    buffer_size: Option<u16>,
+}
+
+#[test]
+fn parses_units() {
+    for (value, expected) in [
+        ("512", 512),
+        ("512b", 512),
+        ("512k", 512_000),
+        ("512kB", 512_000),
+        ("512KB", 512_000),
+        ("4Ki", 4096),
+        ("4KiB", 4096),
+        ("4kib", 4096),
+        ("10MiB", 10 << 20),
+        ("10 MB", 10_000_000),
+        ("1GB", 1_000_000_000),
+        ("1g", 1_000_000_000),
+        ("2GiB", 2 << 30),
+        ("1T", 1_000_000_000_000),
+        ("1TiB", 1 << 40),
+        ("1PB", 1_000_000_000_000_000),
+        ("1PiB", 1 << 50),
+        ("1EB", 1_000_000_000_000_000_000),
+        ("1EiB", 1 << 60),
+        (" 0 ", 0),
+    ] {
+        let source = source(&[("CACHE_SIZE", value)]);
+
+        let config = Config::from_source(&source).finalize().unwrap();
+
+        assert_eq!(Some(expected), config.cache_size, "{value}");
+    }
+}
+
+#[test]
+fn default_is_parsed() {
+    let config = Config::from_source(&source(&[])).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            max_body_size: 10 * 1024 * 1024,
+            cache_size: None,
+            buffer_size: None,
+        },
+        config
+    );
+}
+
+#[test]
+fn errors_describe_the_format() {
+    let source = source(&[
+        ("MAX_BODY_SIZE", "10 megabytes"),
+        ("CACHE_SIZE", "16EiB"),
+        ("BUFFER_SIZE", "64KiB"),
+    ]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"3 configuration errors:
+  1. `Config.max_body_size`: Failed to parse 'MAX_BODY_SIZE'="10 megabytes": unknown unit `megabytes`, expected a byte size such as `512k`, `10MiB` or `1GB` using the units B, kB, MB, GB, TB, PB, EB, KiB, MiB, GiB, TiB, PiB, EiB
+  2. `Config.cache_size`: Failed to parse 'CACHE_SIZE'="16EiB": `16EiB` is too large, the maximum is 18446744073709551615 bytes
+  3. `Config.buffer_size`: Failed to parse 'BUFFER_SIZE'="64KiB": `64KiB` is 65536 bytes, which is too large for `u16`
+"#;
+    assert_eq!(expected, errors.to_string());
+}
+
+#[test]
+fn missing_number() {
+    let source = source(&[("CACHE_SIZE", "MiB")]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    assert!(
+        errors
+            .to_string()
+            .contains("missing number, expected a byte size")
+    );
+}
+
+#[test]
+fn requirements_describe_the_format() {
+    let requirements = Config::requirements_model();
+
+    assert_eq!(Some(Format::ByteSize), requirements[0].format);
+    assert_eq!("fromenv::byte_size", requirements[0].parser);
+
+    let expected =
+        "MAX_BODY_SIZE=10MiB  # usize, optional (default), byte size such as 512k, 10MiB or 1GB
+CACHE_SIZE=  # Option<u64>, optional, byte size such as 512k, 10MiB or 1GB
+BUFFER_SIZE=  # Option<u16>, optional, byte size such as 512k, 10MiB or 1GB
+";
+    assert_eq!(expected, Config::requirements());
+}
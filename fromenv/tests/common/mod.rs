@@ -0,0 +1,7 @@
+use std::collections::HashMap;
+
+pub fn source(vars: &[(&str, &str)]) -> HashMap<String, String> {
+    vars.iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect()
+}
@@ -0,0 +1,125 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+#[allow(unused)]
+pub struct DatabaseConfig {
+    #[env(from = "DATABASE_URL")]
+    url: String,
+    #[env(from = "DATABASE_POOL_SIZE", default = "10")]
+    pool_size: u32,
+}
+
+#[derive(FromEnv)]
+#[allow(unused)]
+pub struct MigrationsConfig {
+    #[env(from = "DATABASE_URL")]
+    url: String,
+    #[env(from = "MIGRATIONS_DIR", default = "migrations")]
+    dir: String,
+}
+
+#[derive(FromEnv)]
+#[allow(unused)]
+pub struct AppConfig {
+    #[env(nested)]
+    database: DatabaseConfig,
+    #[env(nested)]
+    migrations: MigrationsConfig,
+}
+
+#[test]
+fn identical_duplicates_are_consistent() {
+    fromenv::assert_consistent(&AppConfig::requirements_model());
+}
+
+#[test]
+fn identical_duplicates_are_collapsed() {
+    let expected = "\
+        DATABASE_URL=  # String, required\n\
+        DATABASE_POOL_SIZE=10  # u32, optional (default)\n\
+        MIGRATIONS_DIR=migrations  # String, optional (default)\n\
+    ";
+
+    assert_eq!(expected, AppConfig::requirements());
+
+    // The model still includes every field.
+    assert_eq!(4, AppConfig::requirements_model().len());
+}
+
+#[test]
+#[should_panic(expected = "conflicting environment variables:
+  'DATABASE_URL' is read by `ConflictingConfig.database.url` and `ConflictingConfig.cache.url`: default none vs \"redis://localhost\"
+  'DATABASE_POOL_SIZE' is read by `ConflictingConfig.database.pool_size` and `ConflictingConfig.cache.pool_size`: type `u32` vs `Option<u16>`, default \"10\" vs none, not optional vs optional
+")]
+fn conflicting_duplicates_panic() {
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct CacheConfig {
+        #[env(from = "DATABASE_URL", default = "redis://localhost")]
+        url: String,
+        #[env(from = "DATABASE_POOL_SIZE")]
+        pool_size: Option<u16>,
+    }
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct ConflictingConfig {
+        #[env(nested)]
+        database: DatabaseConfig,
+        #[env(nested)]
+        cache: CacheConfig,
+    }
+
+    fromenv::assert_consistent(&ConflictingConfig::requirements_model());
+}
+
+#[test]
+#[should_panic(expected = "parser `from_str` vs `into`")]
+fn conflicting_parsers_panic() {
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "DATABASE_URL")]
+        url: String,
+        #[env(from = "DATABASE_URL", with = into)]
+        url_again: String,
+    }
+
+    fromenv::assert_consistent(&Config::requirements_model());
+}
+
+#[test]
+#[should_panic(expected = "conflicting environment variables:
+  'API_KEY' is read by `Config.api_key` and `Config.api_key_again`: sensitive vs not sensitive, aliases KEY vs none, file fallback vs no file fallback
+")]
+fn conflicting_sensitivity_aliases_and_file_fallback_panic() {
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "API_KEY", alias = "KEY", file_fallback, sensitive)]
+        api_key: String,
+        #[env(from = "API_KEY")]
+        api_key_again: String,
+    }
+
+    fromenv::assert_consistent(&Config::requirements_model());
+}
+
+#[test]
+fn conflicting_duplicates_are_documented_separately() {
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "PORT")]
+        port: u16,
+        #[env(from = "PORT", default = "8080")]
+        port_again: u16,
+    }
+
+    let expected = "\
+        PORT=  # u16, required\n\
+        PORT=8080  # u16, optional (default)\n\
+    ";
+
+    assert_eq!(expected, Config::requirements());
+}
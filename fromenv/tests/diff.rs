@@ -0,0 +1,377 @@
+use fromenv::{ChangeKind, FromEnv, RequirementsDiff, Severity};
+
+mod v1 {
+    use super::*;
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, default = "8080")]
+        pub port: u16,
+        #[env(from)]
+        pub database_url: String,
+        #[env(from)]
+        pub log_level: Option<String>,
+        #[env(from)]
+        pub legacy_mode: Option<bool>,
+        #[env(from)]
+        pub timeout: u32,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, default = "9090")]
+        pub port: u16,
+        #[env(from, default = "postgres://localhost/app")]
+        pub database_url: String,
+        #[env(from)]
+        pub log_level: String,
+        #[env(from)]
+        pub timeout: u64,
+        #[env(from)]
+        pub tracing: Option<bool>,
+        #[env(from)]
+        pub api_key: String,
+    }
+}
+
+#[test]
+fn classifies_changes() {
+    let diff = RequirementsDiff::between(
+        &v1::Config::requirements_model(),
+        &v2::Config::requirements_model(),
+    );
+
+    let actual: Vec<_> = diff
+        .iter()
+        .map(|change| {
+            (
+                change.env_var.as_str(),
+                change.kind.clone(),
+                change.severity,
+            )
+        })
+        .collect();
+
+    let expected = vec![
+        (
+            "PORT",
+            ChangeKind::DefaultChanged {
+                old: "8080".into(),
+                new: "9090".into(),
+            },
+            Severity::Notable,
+        ),
+        (
+            "DATABASE_URL",
+            ChangeKind::BecameOptional,
+            Severity::Compatible,
+        ),
+        ("LOG_LEVEL", ChangeKind::BecameRequired, Severity::Breaking),
+        ("LEGACY_MODE", ChangeKind::Removed, Severity::Notable),
+        (
+            "TIMEOUT",
+            ChangeKind::TypeChanged {
+                old: "u32".into(),
+                new: "u64".into(),
+            },
+            Severity::Breaking,
+        ),
+        (
+            "TRACING",
+            ChangeKind::Added { required: false },
+            Severity::Compatible,
+        ),
+        (
+            "API_KEY",
+            ChangeKind::Added { required: true },
+            Severity::Breaking,
+        ),
+    ];
+
+    assert_eq!(expected, actual);
+    assert_eq!(Some(Severity::Breaking), diff.severity());
+    assert!(diff.is_breaking());
+}
+
+#[test]
+fn diff_display() {
+    let diff = RequirementsDiff::between(
+        &v1::Config::requirements_model(),
+        &v2::Config::requirements_model(),
+    );
+
+    let expected = r#"notable: 'PORT' default changed from "8080" to "9090"
+compatible: 'DATABASE_URL' is no longer required
+breaking: 'LOG_LEVEL' is now required
+notable: 'LEGACY_MODE' is no longer used
+breaking: 'TIMEOUT' type changed from `u32` to `u64`
+compatible: 'TRACING' is a new optional variable
+breaking: 'API_KEY' is a new required variable
+"#;
+
+    assert_eq!(expected, diff.to_string());
+}
+
+#[test]
+fn identical_requirements() {
+    let requirements = v1::Config::requirements_model();
+    let diff = RequirementsDiff::between(&requirements, &requirements);
+
+    assert!(diff.is_empty());
+    assert_eq!(None, diff.severity());
+    assert!(!diff.is_breaking());
+}
+
+#[test]
+fn optional_changes_are_compatible() {
+    let diff = RequirementsDiff::between(
+        &v1::Config::requirements_model(),
+        &v1::Config::requirements_model_with_prefix("APP_"),
+    );
+
+    // Every variable is renamed, so each one is removed and added.
+    assert_eq!(10, diff.len());
+    assert!(diff.is_breaking());
+
+    let added: Vec<_> = diff
+        .into_iter()
+        .filter(|change| matches!(change.kind, ChangeKind::Added { required: false }))
+        .map(|change| change.env_var)
+        .collect();
+
+    assert_eq!(vec!["APP_PORT", "APP_LOG_LEVEL", "APP_LEGACY_MODE"], added);
+}
+
+#[test]
+fn separator_changes_are_breaking() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from, sep = ",")]
+            pub hosts: Vec<String>,
+        }
+    }
+
+    mod after {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from, sep = ";")]
+            pub hosts: Vec<String>,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    assert_eq!(Some(Severity::Breaking), diff.severity());
+    assert_eq!(
+        "breaking: 'HOSTS' format changed from list separated by \",\" to list separated by \";\"\n",
+        diff.to_string()
+    );
+}
+
+#[test]
+fn renames_with_an_alias_are_compatible() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from = "KAFKA_BROKERS")]
+            pub brokers: String,
+        }
+    }
+
+    mod after {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from = "KAFKA_BOOTSTRAP_SERVERS", alias = "KAFKA_BROKERS")]
+            pub brokers: String,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    assert_eq!(Some(Severity::Compatible), diff.severity());
+    assert_eq!(
+        "compatible: 'KAFKA_BOOTSTRAP_SERVERS' was renamed from 'KAFKA_BROKERS', which is still read\n",
+        diff.to_string()
+    );
+
+    // Dropping the alias later breaks deployments that still set it.
+    let diff = RequirementsDiff::between(
+        &after::Config::requirements_model(),
+        &before::Config::requirements_model(),
+    );
+
+    assert!(diff.is_breaking());
+}
+
+#[test]
+fn removing_an_alias_or_file_fallback_is_breaking() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from, alias = "DB_URL", file_fallback)]
+            pub database_url: String,
+        }
+    }
+
+    mod after {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from)]
+            pub database_url: String,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    let expected = r#"breaking: 'DATABASE_URL' no longer reads 'DB_URL'
+breaking: 'DATABASE_URL' can no longer be read from 'DATABASE_URL_FILE'
+"#;
+
+    assert_eq!(expected, diff.to_string());
+}
+
+#[test]
+fn shared_variables_are_compared_once() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Database {
+            #[env(from = "DATABASE_URL")]
+            pub url: String,
+        }
+
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(nested)]
+            pub primary: Database,
+            #[env(nested)]
+            pub replica: Database,
+        }
+    }
+
+    mod after {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Database {
+            #[env(from = "DATABASE_URL", default = "postgres://localhost/app")]
+            pub url: String,
+        }
+
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(nested)]
+            pub primary: Database,
+            #[env(nested)]
+            pub replica: Database,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    assert_eq!(
+        "compatible: 'DATABASE_URL' is no longer required\n",
+        diff.to_string()
+    );
+}
+
+#[test]
+fn type_paths_are_ignored() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from, with = fromenv::duration)]
+            pub timeout: std::time::Duration,
+        }
+    }
+
+    mod after {
+        use std::time::Duration;
+
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from, with = fromenv::duration)]
+            pub timeout: Option<Duration>,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    assert_eq!(
+        "compatible: 'TIMEOUT' is no longer required\n",
+        diff.to_string()
+    );
+}
+
+#[test]
+fn parser_and_sensitivity_changes() {
+    mod before {
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from = "A")]
+            pub a: String,
+            #[env(from = "B", default = "hunter2", sensitive)]
+            pub b: String,
+        }
+    }
+
+    mod after {
+        fn strict(s: &str) -> fromenv::ParseResult<String> {
+            Ok(s.to_owned())
+        }
+
+        #[derive(fromenv::FromEnv)]
+        #[allow(unused)]
+        pub struct Config {
+            #[env(from = "A", with = strict, sensitive)]
+            pub a: String,
+            #[env(from = "B", default = "hunter2")]
+            pub b: String,
+        }
+    }
+
+    let diff = RequirementsDiff::between(
+        &before::Config::requirements_model(),
+        &after::Config::requirements_model(),
+    );
+
+    let expected = r#"notable: 'A' is now sensitive
+breaking: 'A' parser changed from `from_str` to `strict`
+notable: 'B' is no longer sensitive
+"#;
+
+    assert_eq!(expected, diff.to_string());
+    assert!(diff.is_breaking());
+}
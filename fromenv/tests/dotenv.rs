@@ -0,0 +1,124 @@
+use std::collections::HashMap;
+
+use fromenv::{Dotenv, EnvSource, FromEnv};
+
+#[test]
+fn parses_dotenv_syntax() {
+    let contents = r#"
+# A comment
+export DATABASE_URL=postgres://postgres@postgres/postgres
+PORT = 8080 # trailing comment
+LITERAL='single $quoted \n'
+ESCAPED="line one\nline \"two\""
+MULTI_LINE="-----BEGIN KEY-----
+abc
+-----END KEY-----"
+EMPTY=
+PORT=8081
+"#;
+
+    let dotenv = Dotenv::parse(".env", contents).unwrap();
+
+    assert_eq!(
+        dotenv.var("DATABASE_URL").as_deref(),
+        Some("postgres://postgres@postgres/postgres")
+    );
+    assert_eq!(dotenv.var("PORT").as_deref(), Some("8081"));
+    assert_eq!(dotenv.var("LITERAL").as_deref(), Some(r"single $quoted \n"));
+    assert_eq!(
+        dotenv.var("ESCAPED").as_deref(),
+        Some("line one\nline \"two\"")
+    );
+    assert_eq!(
+        dotenv.var("MULTI_LINE").as_deref(),
+        Some("-----BEGIN KEY-----\nabc\n-----END KEY-----")
+    );
+    assert_eq!(dotenv.var("EMPTY").as_deref(), Some(""));
+    assert_eq!(dotenv.var("MISSING"), None);
+}
+
+#[test]
+fn reports_syntax_errors_with_line_numbers() {
+    let contents = "\
+FOO=bar
+not a variable
+1BAD=value
+QUOTED=\"unterminated
+";
+
+    let expected = r#"3 configuration errors:
+  1. `.env:2`: expected `KEY=VALUE`
+  2. `.env:3`: invalid variable name `1BAD`
+  3. `.env:4`: unterminated "-quoted value
+"#;
+    let actual = Dotenv::parse(".env", contents).unwrap_err().to_string();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn reports_missing_file() {
+    let error = Dotenv::from_path("definitely/not/a/.env")
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.starts_with("1 configuration error:\n  1. `definitely/not/a/.env`: "));
+}
+
+#[test]
+fn loads_config_from_file() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from)]
+        database_url: String,
+        #[env(from, default = "8080")]
+        port: u16,
+    }
+
+    let path = std::env::temp_dir().join(format!("fromenv-{}.env", std::process::id()));
+    std::fs::write(&path, "DATABASE_URL=postgres://localhost/db\nPORT=3000\n").unwrap();
+    let dotenv = Dotenv::from_path(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let expected = Config {
+        database_url: "postgres://localhost/db".into(),
+        port: 3000,
+    };
+    let actual = Config::from_source(&dotenv.unwrap()).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn layering_controls_precedence() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from)]
+        port: u16,
+        #[env(from)]
+        host: String,
+    }
+
+    let dotenv = Dotenv::parse(".env", "PORT=3000\nHOST=localhost\n").unwrap();
+    let env = HashMap::from([("PORT".to_owned(), "8080".to_owned())]);
+
+    let below = Config::from_source(&env.clone().or(dotenv.clone()))
+        .finalize()
+        .unwrap();
+    assert_eq!(
+        Config {
+            port: 8080,
+            host: "localhost".into()
+        },
+        below
+    );
+
+    let above = Config::from_source(&dotenv.or(env)).finalize().unwrap();
+    assert_eq!(
+        Config {
+            port: 3000,
+            host: "localhost".into()
+        },
+        above
+    );
+}
@@ -0,0 +1,163 @@
+use std::time::Duration;
+
+use fromenv::{Format, FromEnv};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from = "TIMEOUT", with = fromenv::duration, default = "30s")]
+    timeout: Duration,
+    #[env(from = "INTERVAL", with = fromenv::duration, unit = "ms")]
+    interval: Option<Duration>,
+    #[env(from = "BACKOFF", with = fromenv::duration, sep = ",")]
+    backoff: Vec<Duration>,
+}
+
+#[test]
+fn parses_units() {
+    for (value, expected) in [
+        ("10ns", Duration::from_nanos(10)),
+        ("10us", Duration::from_micros(10)),
+        ("10µs", Duration::from_micros(10)),
+        ("500ms", Duration::from_millis(500)),
+        ("30s", Duration::from_secs(30)),
+        ("5m", Duration::from_secs(300)),
+        ("2h", Duration::from_secs(7200)),
+        ("1d", Duration::from_secs(86400)),
+        ("1h30m", Duration::from_secs(5400)),
+        ("1s500ms", Duration::from_millis(1500)),
+        (" 0s ", Duration::ZERO),
+    ] {
+        let source = source(&[("TIMEOUT", value), ("BACKOFF", "")]);
+
+        let config = Config::from_source(&source).finalize().unwrap();
+
+        assert_eq!(expected, config.timeout, "{value}");
+    }
+}
+
+#[test]
+fn bare_numbers_use_the_unit() {
+    let source = source(&[("INTERVAL", "250"), ("BACKOFF", "1s, 2s,4s")]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            timeout: Duration::from_secs(30),
+            interval: Some(Duration::from_millis(250)),
+            backoff: vec![
+                Duration::from_secs(1),
+                Duration::from_secs(2),
+                Duration::from_secs(4)
+            ],
+        },
+        config
+    );
+}
+
+#[test]
+fn units_override_the_default_unit() {
+    let source = source(&[("INTERVAL", "1m"), ("BACKOFF", "")]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(Some(Duration::from_secs(60)), config.interval);
+}
+
+#[test]
+fn errors_list_the_units() {
+    let source = source(&[
+        ("TIMEOUT", "30"),
+        ("INTERVAL", "1.5s"),
+        ("BACKOFF", "1s,99999999999999999999999999d"),
+    ]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"3 configuration errors:
+  1. `Config.timeout`: Failed to parse 'TIMEOUT'="30": missing unit after `30`, expected a duration such as `30s` or `1h30m` using the units ns, us, µs, ms, s, m, h, d
+  2. `Config.interval`: Failed to parse 'INTERVAL'="1.5s": unknown unit `.`, expected a duration such as `30s` or `1h30m` using the units ns, us, µs, ms, s, m, h, d
+  3. `Config.backoff`: Failed to parse 'BACKOFF'="1s,99999999999999999999999999d": element 1: duration is too large, expected a duration such as `30s` or `1h30m` using the units ns, us, µs, ms, s, m, h, d
+"#;
+    assert_eq!(expected, errors.to_string());
+}
+
+#[test]
+fn empty_and_unknown_units() {
+    let source = source(&[("TIMEOUT", ""), ("INTERVAL", "5 min"), ("BACKOFF", "")]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+    let messages: Vec<_> = errors.iter().map(ToString::to_string).collect();
+
+    assert!(messages[0].contains("empty duration, expected"));
+    assert!(messages[1].contains("unknown unit ` min`, expected"));
+}
+
+#[test]
+fn requirements_describe_the_format() {
+    let requirements = Config::requirements_model();
+
+    assert_eq!(
+        Some(Format::Duration { unit: None }),
+        requirements[0].format
+    );
+    assert_eq!("fromenv::duration", requirements[0].parser);
+    assert_eq!(
+        Some(Format::Duration {
+            unit: Some("ms".into())
+        }),
+        requirements[1].format
+    );
+    assert_eq!(
+        Some(Format::List { sep: ",".into() }),
+        requirements[2].format
+    );
+
+    let expected = "TIMEOUT=30s  # Duration, optional (default), duration such as 30s or 1h30m
+INTERVAL=  # Option<Duration>, optional, duration such as 30s or 1h30m, bare numbers in ms
+BACKOFF=  # Vec<Duration>, required, list separated by \",\"
+";
+    assert_eq!(expected, Config::requirements());
+}
+
+#[test]
+fn local_duration_functions_are_not_replaced() {
+    mod local {
+        use std::time::Duration;
+
+        use fromenv::{FromEnv, ParseResult};
+
+        #[derive(FromEnv, Debug)]
+        pub struct Config {
+            #[env(from = "TIMEOUT", with = duration)]
+            pub timeout: Duration,
+            #[env(from = "SIZE", with = byte_size)]
+            pub size: u64,
+        }
+
+        fn duration(s: &str) -> ParseResult<Duration> {
+            Ok(Duration::from_secs(s.parse()?))
+        }
+
+        fn byte_size(s: &str) -> ParseResult<u64> {
+            Ok(s.parse::<u64>()? * 4096)
+        }
+    }
+
+    let source = source(&[("TIMEOUT", "30"), ("SIZE", "2")]);
+
+    let config = local::Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(Duration::from_secs(30), config.timeout);
+    assert_eq!(8192, config.size);
+
+    let requirements = local::Config::requirements_model();
+    assert_eq!("duration", requirements[0].parser);
+    assert_eq!(None, requirements[0].format);
+    assert_eq!("byte_size", requirements[1].parser);
+    assert_eq!(None, requirements[1].format);
+}
@@ -57,7 +57,7 @@ fn captures_nested_errors() {
 
     let expected = r#"2 configuration errors:
   1. `AppConfig.database_url`: Missing required environment variable 'DATABASE_URL'
-  2. `KafkaConfig.broker`: Missing required environment variable 'KAFKA_BROKER'
+  2. `AppConfig.kafka.broker`: Missing required environment variable 'KAFKA_BROKER'
 "#;
     let actual = AppConfig::from_env().finalize().unwrap_err().to_string();
 
@@ -104,3 +104,60 @@ fn parse_error() {
 
     assert_eq!(expected, actual);
 }
+
+#[test]
+fn inspect_individual_errors() {
+    use std::{collections::HashMap, error::Error};
+
+    use fromenv::{FromEnv, FromEnvErrorKind};
+
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from)]
+        database_url: String,
+        #[env(from)]
+        port: u16,
+    }
+
+    let source = HashMap::from([("PORT".to_owned(), "invalid".to_owned())]);
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    assert_eq!(2, errors.len());
+
+    let actual: Vec<_> = errors
+        .iter()
+        .map(|error| {
+            (
+                error.kind(),
+                error.path().unwrap().as_str(),
+                error.env_var(),
+                error.value(),
+            )
+        })
+        .collect();
+    let expected = vec![
+        (
+            FromEnvErrorKind::MissingEnv,
+            "Config.database_url",
+            Some("DATABASE_URL"),
+            None,
+        ),
+        (
+            FromEnvErrorKind::ParseError,
+            "Config.port",
+            Some("PORT"),
+            Some("invalid"),
+        ),
+    ];
+    assert_eq!(expected, actual);
+
+    let sources: Vec<_> = errors
+        .into_iter()
+        .map(|error| error.source().map(ToString::to_string))
+        .collect();
+    assert_eq!(
+        vec![None, Some("invalid digit found in string".to_owned())],
+        sources
+    );
+}
@@ -0,0 +1,75 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from = "DB_PASSWORD", file_fallback)]
+    password: String,
+}
+
+fn secret_file(name: &str, contents: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("fromenv-{}-{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+    path
+}
+
+#[test]
+fn reads_file_when_variable_is_unset() {
+    let path = secret_file("password", "hunter2\n");
+
+    let source = HashMap::from([(
+        "DB_PASSWORD_FILE".to_owned(),
+        path.to_str().unwrap().to_owned(),
+    )]);
+    let actual = Config::from_source(&source).finalize();
+    std::fs::remove_file(&path).unwrap();
+
+    let expected = Config {
+        password: "hunter2".into(),
+    };
+
+    assert_eq!(expected, actual.unwrap());
+}
+
+#[test]
+fn variable_takes_precedence_over_file() {
+    let source = HashMap::from([
+        ("DB_PASSWORD".to_owned(), "from-env".to_owned()),
+        (
+            "DB_PASSWORD_FILE".to_owned(),
+            "/definitely/not/a/file".to_owned(),
+        ),
+    ]);
+
+    let expected = Config {
+        password: "from-env".into(),
+    };
+    let actual = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn unreadable_file_is_reported() {
+    let source = HashMap::from([(
+        "DB_PASSWORD_FILE".to_owned(),
+        "/definitely/not/a/file".to_owned(),
+    )]);
+
+    let actual = Config::from_source(&source)
+        .finalize()
+        .unwrap_err()
+        .to_string();
+
+    assert!(actual.starts_with(
+        "1 configuration error:\n  1. `Config.password`: Failed to read 'DB_PASSWORD_FILE'=\"/definitely/not/a/file\": "
+    ));
+}
+
+#[test]
+fn requirements_document_file_variable() {
+    let expected = "DB_PASSWORD=  # String, required, or DB_PASSWORD_FILE\n";
+
+    assert_eq!(expected, Config::requirements());
+}
@@ -0,0 +1,104 @@
+use std::error::Error;
+
+use fromenv::{Format, FromEnv, FromEnvError, ListElementError, ParseResult};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from = "HOSTS", sep = ",")]
+    hosts: Vec<String>,
+    #[env(from = "PORTS", sep = ";", default = "80;443")]
+    ports: Vec<u16>,
+    #[env(from = "RETRIES", sep = ",", with = retries)]
+    retries: Option<Vec<u8>>,
+}
+
+fn retries(s: &str) -> ParseResult<u8> {
+    match s {
+        "none" => Ok(0),
+        s => Ok(s.parse()?),
+    }
+}
+
+#[test]
+fn elements_are_split_and_trimmed() {
+    let source = source(&[
+        ("HOSTS", "a.example.com, b.example.com ,c.example.com"),
+        ("RETRIES", "1,none"),
+    ]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            hosts: vec![
+                "a.example.com".into(),
+                "b.example.com".into(),
+                "c.example.com".into()
+            ],
+            ports: vec![80, 443],
+            retries: Some(vec![1, 0]),
+        },
+        config
+    );
+}
+
+#[test]
+fn empty_value_is_an_empty_list() {
+    let source = source(&[("HOSTS", ""), ("PORTS", " ")]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            hosts: Vec::new(),
+            ports: Vec::new(),
+            retries: None,
+        },
+        config
+    );
+}
+
+#[test]
+fn errors_identify_the_element() {
+    let source = source(&[("HOSTS", "a"), ("PORTS", "80;;443"), ("RETRIES", "1,x")]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"2 configuration errors:
+  1. `Config.ports`: Failed to parse 'PORTS'="80;;443": element 1: cannot parse integer from empty string
+  2. `Config.retries`: Failed to parse 'RETRIES'="1,x": element 1: invalid digit found in string
+"#;
+    assert_eq!(expected, errors.to_string());
+
+    let error = errors.iter().next().unwrap();
+    assert!(matches!(error, FromEnvError::ParseError { .. }));
+    let element = error
+        .source()
+        .unwrap()
+        .downcast_ref::<ListElementError>()
+        .unwrap();
+    assert_eq!(1, element.index());
+}
+
+#[test]
+fn requirements_show_the_separator() {
+    let requirements = Config::requirements_model();
+
+    assert_eq!(
+        Some(Format::List { sep: ",".into() }),
+        requirements[0].format
+    );
+    assert_eq!("from_str", requirements[0].parser);
+    assert_eq!("retries", requirements[2].parser);
+
+    assert_eq!(
+        "HOSTS=  # Vec<String>, required, list separated by \",\"\n\
+         PORTS=80;443  # Vec<u16>, optional (default), list separated by \";\"\n\
+         RETRIES=  # Option<Vec<u8>>, optional, list separated by \",\"\n",
+        Config::requirements()
+    );
+}
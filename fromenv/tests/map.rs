@@ -0,0 +1,147 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    error::Error,
+};
+
+use fromenv::{Format, FromEnv, MapError, ParseResult};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from = "ATTRIBUTES", sep = ",", kv_sep = "=")]
+    attributes: BTreeMap<String, String>,
+    #[env(from = "LIMITS", sep = ";", kv_sep = ":", default = "")]
+    limits: HashMap<String, u32>,
+    #[env(from = "WEIGHTS", sep = ",", kv_sep = "=", with = weight)]
+    weights: Option<BTreeMap<u8, u8>>,
+}
+
+fn weight(s: &str) -> ParseResult<u8> {
+    match s {
+        "max" => Ok(u8::MAX),
+        s => Ok(s.parse()?),
+    }
+}
+
+#[test]
+fn pairs_are_split_trimmed_and_decoded() {
+    let source = source(&[
+        (
+            "ATTRIBUTES",
+            "service.name=api, env = prod,note=a%2Cb%3Dc%25,token=abc==",
+        ),
+        ("LIMITS", "cpu:2;memory:512"),
+        ("WEIGHTS", "1=10,2=max"),
+    ]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            attributes: BTreeMap::from([
+                ("service.name".into(), "api".into()),
+                ("env".into(), "prod".into()),
+                ("note".into(), "a,b=c%".into()),
+                ("token".into(), "abc==".into()),
+            ]),
+            limits: HashMap::from([("cpu".into(), 2), ("memory".into(), 512)]),
+            weights: Some(BTreeMap::from([(1, 10), (2, u8::MAX)])),
+        },
+        config
+    );
+}
+
+#[test]
+fn empty_value_is_an_empty_map() {
+    let source = source(&[("ATTRIBUTES", "")]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            attributes: BTreeMap::new(),
+            limits: HashMap::new(),
+            weights: None,
+        },
+        config
+    );
+}
+
+#[test]
+fn errors_identify_the_pair() {
+    let source = source(&[
+        ("ATTRIBUTES", "env=prod,env=dev"),
+        ("LIMITS", "cpu:2;memory"),
+        ("WEIGHTS", "1=10,x=1,3=%zz"),
+    ]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"3 configuration errors:
+  1. `Config.attributes`: Failed to parse 'ATTRIBUTES'="env=prod,env=dev": pair 1: duplicate key
+  2. `Config.limits`: Failed to parse 'LIMITS'="cpu:2;memory": pair 1: expected `KEY:VALUE`
+  3. `Config.weights`: Failed to parse 'WEIGHTS'="1=10,x=1,3=%zz": pair 1: invalid key: invalid digit found in string
+"#;
+    assert_eq!(expected, errors.to_string());
+
+    let kinds: Vec<_> = errors
+        .iter()
+        .map(|error| error.source().unwrap().downcast_ref::<MapError>().unwrap())
+        .collect();
+    assert!(matches!(kinds[0], MapError::DuplicateKey { index: 1 }));
+    assert!(matches!(kinds[1], MapError::MalformedPair { index: 1, .. }));
+    assert!(matches!(kinds[2], MapError::InvalidKey { index: 1, .. }));
+}
+
+#[test]
+fn stray_percent_signs_are_literal() {
+    let source = source(&[("ATTRIBUTES", "limit=50%,ratio=%zz,end=100%2")]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        BTreeMap::from([
+            ("limit".into(), "50%".into()),
+            ("ratio".into(), "%zz".into()),
+            ("end".into(), "100%2".into()),
+        ]),
+        config.attributes
+    );
+}
+
+#[test]
+fn invalid_percent_encoding() {
+    let source = source(&[("ATTRIBUTES", "a=%C3%28"), ("WEIGHTS", "1=%FF")]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"2 configuration errors:
+  1. `Config.attributes`: Failed to parse 'ATTRIBUTES'="a=%C3%28": pair 0: invalid percent-encoding
+  2. `Config.weights`: Failed to parse 'WEIGHTS'="1=%FF": pair 0: invalid percent-encoding
+"#;
+    assert_eq!(expected, errors.to_string());
+}
+
+#[test]
+fn requirements_show_the_separators() {
+    let requirements = Config::requirements_model();
+
+    assert_eq!(
+        Some(Format::Map {
+            sep: ",".into(),
+            kv_sep: "=".into()
+        }),
+        requirements[0].format
+    );
+    assert_eq!("weight", requirements[2].parser);
+
+    assert_eq!(
+        "ATTRIBUTES=  # BTreeMap<String, String>, required, map of KEY=VALUE pairs separated by \",\"\n\
+         LIMITS=  # HashMap<String, u32>, optional (default), map of KEY:VALUE pairs separated by \";\"\n\
+         WEIGHTS=  # Option<BTreeMap<u8, u8>>, optional, map of KEY=VALUE pairs separated by \",\"\n",
+        Config::requirements()
+    );
+}
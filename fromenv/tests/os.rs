@@ -0,0 +1,83 @@
+#![cfg(unix)]
+
+use std::{
+    ffi::{OsStr, OsString},
+    os::unix::ffi::{OsStrExt, OsStringExt},
+    path::PathBuf,
+};
+
+use fromenv::{EnvSource, FromEnv};
+
+/// A source where every variable holds a path that isn't valid unicode.
+struct NonUnicode;
+
+impl EnvSource for NonUnicode {
+    fn var(&self, _key: &str) -> Option<String> {
+        None
+    }
+
+    fn var_os(&self, _key: &str) -> Option<OsString> {
+        Some(OsString::from_vec(b"/tmp/caf\xe9".to_vec()))
+    }
+}
+
+#[test]
+fn non_unicode_value_is_not_reported_as_missing() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "DATA_DIR", default = "/var/lib/data")]
+        data_dir: String,
+        #[env(from = "CACHE_DIR")]
+        cache_dir: Option<String>,
+    }
+
+    let expected = r#"2 configuration errors:
+  1. `Config.data_dir`: Environment variable 'DATA_DIR' is not valid unicode
+  2. `Config.cache_dir`: Environment variable 'CACHE_DIR' is not valid unicode
+"#;
+    let actual = Config::from_source(&NonUnicode)
+        .finalize()
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn os_fields_accept_non_unicode_values() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from = "DATA_DIR", os)]
+        data_dir: PathBuf,
+        #[env(from = "CACHE_DIR", os)]
+        cache_dir: Option<OsString>,
+    }
+
+    let expected = Config {
+        data_dir: PathBuf::from(OsStr::from_bytes(b"/tmp/caf\xe9")),
+        cache_dir: Some(OsString::from_vec(b"/tmp/caf\xe9".to_vec())),
+    };
+    let actual = Config::from_source(&NonUnicode).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn os_fields_use_defaults() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from = "DATA_DIR", os, default = "/var/lib/data")]
+        data_dir: PathBuf,
+        #[env(from = "CACHE_DIR", os)]
+        cache_dir: Option<PathBuf>,
+    }
+
+    let expected = Config {
+        data_dir: PathBuf::from("/var/lib/data"),
+        cache_dir: None,
+    };
+    let actual = Config::from_source(&|_: &str| None).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct PostgresConfig {
+    #[env(from = "PG_HOST", default = "localhost")]
+    host: String,
+    #[env(from = "PG_PORT", default = "5432")]
+    port: u16,
+    #[env(from = "PG_PASSWORD", file_fallback)]
+    password: String,
+}
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct AppConfig {
+    #[env(nested)]
+    primary: PostgresConfig,
+    #[env(nested, prefix = "REPLICA_")]
+    replica: PostgresConfig,
+}
+
+#[test]
+fn same_nested_type_with_prefix() {
+    let expected = AppConfig {
+        primary: PostgresConfig {
+            host: "primary".into(),
+            port: 5432,
+            password: "primary-password".into(),
+        },
+        replica: PostgresConfig {
+            host: "replica".into(),
+            port: 5433,
+            password: "replica-password".into(),
+        },
+    };
+
+    let source = HashMap::from([
+        ("PG_HOST".to_owned(), "primary".to_owned()),
+        ("PG_PASSWORD".to_owned(), "primary-password".to_owned()),
+        ("REPLICA_PG_HOST".to_owned(), "replica".to_owned()),
+        ("REPLICA_PG_PORT".to_owned(), "5433".to_owned()),
+        (
+            "REPLICA_PG_PASSWORD".to_owned(),
+            "replica-password".to_owned(),
+        ),
+    ]);
+    let actual = AppConfig::from_source(&source).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn errors_use_prefixed_names() {
+    let expected = r#"2 configuration errors:
+  1. `AppConfig.primary.password`: Missing required environment variable 'PG_PASSWORD'
+  2. `AppConfig.replica.port`: Failed to parse 'REPLICA_PG_PORT'="not a port": invalid digit found in string
+"#;
+
+    let source = HashMap::from([
+        ("REPLICA_PG_PORT".to_owned(), "not a port".to_owned()),
+        (
+            "REPLICA_PG_PASSWORD".to_owned(),
+            "replica-password".to_owned(),
+        ),
+    ]);
+    let actual = AppConfig::from_source(&source)
+        .finalize()
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn requirements_use_prefixed_names() {
+    let expected = "\
+        PG_HOST=localhost  # String, optional (default)\n\
+        PG_PORT=5432  # u16, optional (default)\n\
+        PG_PASSWORD=  # String, required, or PG_PASSWORD_FILE\n\
+        REPLICA_PG_HOST=localhost  # String, optional (default)\n\
+        REPLICA_PG_PORT=5432  # u16, optional (default)\n\
+        REPLICA_PG_PASSWORD=  # String, required, or REPLICA_PG_PASSWORD_FILE\n\
+    ";
+
+    assert_eq!(expected, AppConfig::requirements());
+}
+
+#[test]
+fn prefixes_compose() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Databases {
+        #[env(nested, prefix = "REPLICA_")]
+        replica: PostgresConfig,
+    }
+
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(nested, prefix = "BILLING_")]
+        billing: Databases,
+    }
+
+    let expected = Config {
+        billing: Databases {
+            replica: PostgresConfig {
+                host: "localhost".into(),
+                port: 5432,
+                password: "password".into(),
+            },
+        },
+    };
+
+    let source = HashMap::from([(
+        "BILLING_REPLICA_PG_PASSWORD".to_owned(),
+        "password".to_owned(),
+    )]);
+    let actual = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct TenantConfig {
+    #[env(from = "API_URL")]
+    api_url: String,
+    #[env(nested, prefix = "REPLICA_")]
+    replica: PostgresConfig,
+}
+
+#[test]
+fn runtime_prefix() {
+    let expected = TenantConfig {
+        api_url: "https://acme.example.com".into(),
+        replica: PostgresConfig {
+            host: "acme-replica".into(),
+            port: 5432,
+            password: "password".into(),
+        },
+    };
+
+    let source = HashMap::from([
+        ("API_URL".to_owned(), "https://example.com".to_owned()),
+        (
+            "ACME_API_URL".to_owned(),
+            "https://acme.example.com".to_owned(),
+        ),
+        ("ACME_REPLICA_PG_HOST".to_owned(), "acme-replica".to_owned()),
+        ("ACME_REPLICA_PG_PASSWORD".to_owned(), "password".to_owned()),
+    ]);
+    let actual = TenantConfig::from_source_with_prefix(&source, "ACME_")
+        .finalize()
+        .unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn runtime_prefix_from_env() {
+    let expected = TenantConfig {
+        api_url: "https://globex.example.com".into(),
+        replica: PostgresConfig {
+            host: "localhost".into(),
+            port: 5432,
+            password: "password".into(),
+        },
+    };
+
+    let actual = temp_env::with_vars(
+        [
+            ("GLOBEX_API_URL", Some("https://globex.example.com")),
+            ("GLOBEX_REPLICA_PG_PASSWORD", Some("password")),
+        ],
+        || TenantConfig::from_env_with_prefix("GLOBEX_").finalize(),
+    )
+    .unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn runtime_prefix_errors_and_requirements() {
+    let expected = r#"2 configuration errors:
+  1. `TenantConfig.api_url`: Missing required environment variable 'ACME_API_URL'
+  2. `TenantConfig.replica.password`: Missing required environment variable 'ACME_REPLICA_PG_PASSWORD'
+"#;
+    let actual = TenantConfig::from_source_with_prefix(&HashMap::new(), "ACME_")
+        .finalize()
+        .unwrap_err()
+        .to_string();
+
+    assert_eq!(expected, actual);
+
+    let expected = "\
+        ACME_API_URL=  # String, required\n\
+        ACME_REPLICA_PG_HOST=localhost  # String, optional (default)\n\
+        ACME_REPLICA_PG_PORT=5432  # u16, optional (default)\n\
+        ACME_REPLICA_PG_PASSWORD=  # String, required, or ACME_REPLICA_PG_PASSWORD_FILE\n\
+    ";
+
+    assert_eq!(expected, TenantConfig::requirements_with_prefix("ACME_"));
+}
+
+#[test]
+fn warnings_use_full_paths() {
+    use fromenv::FromEnvWarning;
+
+    let source = HashMap::from([
+        ("PG_PASSWORD".to_owned(), "primary-password".to_owned()),
+        ("REPLICA_PG_HOST".to_owned(), "replica".to_owned()),
+        (
+            "REPLICA_PG_PASSWORD".to_owned(),
+            "replica-password".to_owned(),
+        ),
+    ]);
+    let (_, warnings) = AppConfig::from_source(&source)
+        .finalize_with_report()
+        .unwrap();
+
+    let paths: Vec<_> = warnings
+        .iter()
+        .map(|warning| match warning {
+            FromEnvWarning::DefaultApplied { path, .. } => (path.as_str(), path.local()),
+            _ => panic!("unexpected warning: {warning}"),
+        })
+        .collect();
+
+    let expected = vec![
+        ("AppConfig.primary.host", "PostgresConfig.host"),
+        ("AppConfig.primary.port", "PostgresConfig.port"),
+        ("AppConfig.replica.port", "PostgresConfig.port"),
+    ];
+
+    assert_eq!(expected, paths);
+}
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, HashMap};
+
+use fromenv::{Dotenv, EnvSource, FieldPath, FromEnv, FromEnvWarning, ParseResult};
+
+mod common;
+
+use common::source;
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(prefix_map = "LABEL_")]
+    labels: BTreeMap<String, String>,
+    /// Feature toggles.
+    #[env(prefix_map = "FEATURE_", lowercase)]
+    features: HashMap<String, bool>,
+    #[env(prefix_map = "LIMIT_", with = limit, sensitive)]
+    limits: BTreeMap<String, u32>,
+}
+
+fn limit(s: &str) -> ParseResult<u32> {
+    match s {
+        "unlimited" => Ok(u32::MAX),
+        s => Ok(s.parse()?),
+    }
+}
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct AppConfig {
+    #[env(nested, prefix = "APP_")]
+    config: Config,
+}
+
+#[test]
+fn collects_matching_variables() {
+    let source = source(&[
+        ("LABEL_TEAM", "payments"),
+        ("LABEL_TIER", "backend"),
+        ("LABEL_", "ignored"),
+        ("FEATURE_NEW_UI", "true"),
+        ("FEATURE_Beta", "false"),
+        ("LIMIT_REQUESTS", "unlimited"),
+        ("OTHER", "ignored"),
+    ]);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        Config {
+            labels: BTreeMap::from([
+                ("TEAM".into(), "payments".into()),
+                ("TIER".into(), "backend".into()),
+            ]),
+            features: HashMap::from([("new_ui".into(), true), ("beta".into(), false)]),
+            limits: BTreeMap::from([("REQUESTS".into(), u32::MAX)]),
+        },
+        config
+    );
+}
+
+#[test]
+fn no_matching_variables() {
+    let config = Config::from_source(&source(&[])).finalize().unwrap();
+
+    assert!(config.labels.is_empty());
+    assert!(config.features.is_empty());
+    assert!(config.limits.is_empty());
+}
+
+#[test]
+fn nested_prefix_is_applied() {
+    let source = source(&[("APP_LABEL_TEAM", "payments"), ("LABEL_TIER", "backend")]);
+
+    let config = AppConfig::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        BTreeMap::from([("TEAM".into(), "payments".into())]),
+        config.config.labels
+    );
+}
+
+#[test]
+fn layered_sources_are_combined() {
+    let dotenv = Dotenv::parse(".env", "LABEL_TEAM=dotenv\nLABEL_TIER=backend\n").unwrap();
+
+    let source = source(&[("LABEL_TEAM", "payments")]).or(dotenv);
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(
+        BTreeMap::from([
+            ("TEAM".into(), "payments".into()),
+            ("TIER".into(), "backend".into()),
+        ]),
+        config.labels
+    );
+}
+
+#[test]
+fn closures_are_never_listed() {
+    let source = |key: &str| (key == "LABEL_TEAM").then(|| "payments".to_owned());
+
+    let config = Config::from_source(&source).finalize().unwrap();
+
+    assert!(config.labels.is_empty());
+}
+
+#[test]
+fn errors_name_the_variable() {
+    let source = source(&[
+        ("FEATURE_NEW_UI", "yes"),
+        ("FEATURE_BETA", "true"),
+        ("FEATURE_beta", "false"),
+        ("LIMIT_REQUESTS", "lots"),
+    ]);
+
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    let expected = r#"3 configuration errors:
+  1. `Config.features`: Failed to parse 'FEATURE_NEW_UI'="yes": provided string was not `true` or `false`
+  2. `Config.features`: Failed to parse 'FEATURE_beta'="false": key `beta` is also read from 'FEATURE_BETA'
+  3. `Config.limits`: Failed to parse 'LIMIT_REQUESTS'="***": details redacted
+"#;
+    assert_eq!(expected, errors.to_string());
+}
+
+#[test]
+fn empty_values_are_warned_about() {
+    let source = source(&[("LABEL_TEAM", "")]);
+
+    let (config, warnings) = Config::from_source(&source).finalize_with_report().unwrap();
+
+    assert_eq!("", config.labels["TEAM"]);
+    assert_eq!(
+        vec![FromEnvWarning::EmptyValue {
+            path: FieldPath::new("Config.labels", "Config.labels"),
+            env_var: "LABEL_TEAM".into(),
+        }],
+        warnings.into_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn requirements_show_the_pattern() {
+    let expected = "LABEL_*=  # BTreeMap<String, String>, optional
+# Feature toggles.
+FEATURE_*=  # HashMap<String, bool>, optional
+LIMIT_*=  # BTreeMap<String, u32>, optional, sensitive
+";
+    assert_eq!(expected, Config::requirements());
+
+    let requirements = AppConfig::requirements_model();
+    assert_eq!("APP_LABEL_*", requirements[0].env_var);
+    assert!(requirements[0].pattern);
+    assert_eq!("limit", requirements[2].parser);
+}
@@ -0,0 +1,467 @@
+use fromenv::{Dotenv, EnvSource, FromEnv, render};
+
+/// Kafka connection settings.
+#[derive(FromEnv, Debug)]
+#[allow(unused)]
+pub struct KafkaConfig {
+    /// Comma separated list of brokers.
+    #[env(from = "KAFKA_BROKERS")]
+    brokers: String,
+    #[env(from = "KAFKA_TOPIC", default = "events")]
+    topic: String,
+}
+
+#[derive(FromEnv, Debug)]
+#[allow(unused)]
+pub struct Config {
+    #[env(from)]
+    database_url: String,
+    /// The port to listen on.
+    #[env(from, default = "8080")]
+    port: u16,
+    #[env(from, file_fallback, sensitive)]
+    api_key: Option<String>,
+    #[env(nested)]
+    kafka: KafkaConfig,
+}
+
+#[test]
+fn env_compat() {
+    let expected = r#"DATABASE_URL=
+PORT=8080
+API_KEY=
+KAFKA_BROKERS=
+KAFKA_TOPIC=events
+"#;
+
+    assert_eq!(expected, render::env_compat(&Config::requirements_model()));
+}
+
+#[test]
+fn dotenv_example() {
+    let expected = r#"# String, required
+DATABASE_URL=
+
+# The port to listen on.
+# u16, optional (default)
+# PORT=8080
+
+# Option<String>, optional, sensitive, or API_KEY_FILE
+# API_KEY=
+
+## Kafka connection settings.
+
+# Comma separated list of brokers.
+# String, required
+KAFKA_BROKERS=
+
+# String, optional (default)
+# KAFKA_TOPIC=events
+"#;
+
+    assert_eq!(
+        expected,
+        render::dotenv_example(&Config::requirements_model())
+    );
+}
+
+#[test]
+fn dotenv_example_is_a_valid_dotenv_file() {
+    let example = render::dotenv_example(&Config::requirements_model());
+    let dotenv = Dotenv::parse(".env.example", &example).unwrap();
+
+    assert_eq!(Some(String::new()), dotenv.var("DATABASE_URL"));
+    assert_eq!(Some(String::new()), dotenv.var("KAFKA_BROKERS"));
+    assert_eq!(None, dotenv.var("PORT"));
+}
+
+#[test]
+fn write_if_changed() {
+    let path = std::env::temp_dir().join(format!("fromenv-{}.env.example", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+
+    let example = render::dotenv_example(&Config::requirements_model());
+
+    assert!(render::write_if_changed(&path, &example).unwrap());
+    assert!(!render::write_if_changed(&path, &example).unwrap());
+    assert_eq!(example, std::fs::read_to_string(&path).unwrap());
+
+    assert!(render::write_if_changed(&path, "PORT=8080\n").unwrap());
+    assert_eq!("PORT=8080\n", std::fs::read_to_string(&path).unwrap());
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn markdown() {
+    let expected = r#"| Variable | Type | Default | Required | Sensitive | Description |
+| --- | --- | --- | --- | --- | --- |
+| `DATABASE_URL` | `String` |  | required | no |  |
+| `PORT` | `u16` | `8080` | optional | no | The port to listen on. |
+| `API_KEY` | `Option<String>` |  | optional | yes | Can also be read from the file named by `API_KEY_FILE`. |
+
+### `kafka`
+
+Kafka connection settings.
+
+| Variable | Type | Default | Required | Sensitive | Description |
+| --- | --- | --- | --- | --- | --- |
+| `KAFKA_BROKERS` | `String` |  | required | no | Comma separated list of brokers. |
+| `KAFKA_TOPIC` | `String` | `events` | optional | no |  |
+"#;
+
+    assert_eq!(expected, render::markdown(&Config::requirements_model()));
+}
+
+#[test]
+fn markdown_escapes_cells() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct TlsConfig {
+        #[env(from = "TLS_CERT", alias = "CERT")]
+        cert: String,
+    }
+
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(nested)]
+        tls: TlsConfig,
+        /// Either `a|b` or `c`.
+        ///
+        /// Defaults to
+        /// `a|b`.
+        #[env(from, default = "a|b")]
+        mode: String,
+    }
+
+    let expected = r#"### `tls`
+
+| Variable | Type | Default | Required | Sensitive | Description |
+| --- | --- | --- | --- | --- | --- |
+| `TLS_CERT` | `String` |  | required | no | Deprecated aliases: `CERT`. |
+
+### `Config`
+
+| Variable | Type | Default | Required | Sensitive | Description |
+| --- | --- | --- | --- | --- | --- |
+| `MODE` | `String` | `a\|b` | optional | no | Either `a\|b` or `c`.<br><br>Defaults to `a\|b`. |
+"#;
+
+    assert_eq!(expected, render::markdown(&Config::requirements_model()));
+}
+
+#[test]
+fn json_schema() {
+    /// Service settings.
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        /// The port to listen on.
+        #[env(from, default = "8080")]
+        port: u16,
+        #[env(from)]
+        offset: Option<i64>,
+        #[env(from, default = "false")]
+        debug: bool,
+        /// Say "hello".
+        #[env(from, alias = "GREETING")]
+        message: String,
+        #[env(from, file_fallback, sensitive)]
+        api_key: String,
+        #[env(from)]
+        database_url: String,
+    }
+
+    let expected = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Config",
+  "type": "object",
+  "properties": {
+    "PORT": {
+      "type": "string",
+      "description": "The port to listen on.",
+      "default": "8080",
+      "pattern": "^\\+?[0-9]+$"
+    },
+    "OFFSET": {
+      "type": "string",
+      "pattern": "^[+-]?[0-9]+$"
+    },
+    "DEBUG": {
+      "type": "string",
+      "default": "false",
+      "enum": [
+        "true",
+        "false"
+      ]
+    },
+    "MESSAGE": {
+      "type": "string",
+      "description": "Say \"hello\"."
+    },
+    "GREETING": {
+      "type": "string",
+      "description": "Deprecated alias for MESSAGE.",
+      "deprecated": true
+    },
+    "API_KEY": {
+      "type": "string",
+      "writeOnly": true
+    },
+    "API_KEY_FILE": {
+      "type": "string",
+      "description": "Path to a file containing the value of API_KEY."
+    },
+    "DATABASE_URL": {
+      "type": "string"
+    }
+  },
+  "required": [
+    "DATABASE_URL"
+  ],
+  "allOf": [
+    {
+      "anyOf": [
+        {
+          "required": [
+            "MESSAGE"
+          ]
+        },
+        {
+          "required": [
+            "GREETING"
+          ]
+        }
+      ]
+    },
+    {
+      "anyOf": [
+        {
+          "required": [
+            "API_KEY"
+          ]
+        },
+        {
+          "required": [
+            "API_KEY_FILE"
+          ]
+        }
+      ]
+    }
+  ]
+}
+"#;
+
+    assert_eq!(expected, Config::json_schema());
+}
+
+#[test]
+fn json_schema_custom_parser_has_no_constraints() {
+    fn port(s: &str) -> fromenv::ParseResult<u16> {
+        Ok(s.trim_start_matches(':').parse()?)
+    }
+
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, with = port)]
+        port: Option<u16>,
+    }
+
+    let expected = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Config",
+  "type": "object",
+  "properties": {
+    "PORT": {
+      "type": "string"
+    }
+  },
+  "required": []
+}
+"#;
+
+    assert_eq!(expected, Config::json_schema());
+}
+
+#[test]
+fn json_schema_durations() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, with = fromenv::duration)]
+        timeout: std::time::Duration,
+        #[env(from, with = fromenv::duration, unit = "s")]
+        interval: std::time::Duration,
+    }
+
+    let expected = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Config",
+  "type": "object",
+  "properties": {
+    "TIMEOUT": {
+      "type": "string",
+      "pattern": "^([0-9]+(ns|us|µs|ms|s|m|h|d))+$"
+    },
+    "INTERVAL": {
+      "type": "string",
+      "pattern": "^(([0-9]+(ns|us|µs|ms|s|m|h|d))+|[0-9]+)$"
+    }
+  },
+  "required": [
+    "TIMEOUT",
+    "INTERVAL"
+  ]
+}
+"#;
+
+    assert_eq!(expected, Config::json_schema());
+}
+
+#[test]
+fn json_schema_byte_sizes() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, with = fromenv::byte_size, default = "10MiB")]
+        max_body_size: u64,
+    }
+
+    let expected = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Config",
+  "type": "object",
+  "properties": {
+    "MAX_BODY_SIZE": {
+      "type": "string",
+      "default": "10MiB",
+      "pattern": "^[0-9]+ *([kKmMgGtTpPeE][iI]?[bB]?|[bB])?$"
+    }
+  },
+  "required": []
+}
+"#;
+
+    assert_eq!(expected, Config::json_schema());
+}
+
+#[test]
+fn prefix_maps() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        /// Labels attached to metrics.
+        #[env(prefix_map = "LABEL_")]
+        labels: std::collections::HashMap<String, String>,
+        #[env(prefix_map = "TOKEN_", sensitive)]
+        tokens: std::collections::HashMap<String, String>,
+    }
+
+    let expected = r#"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "title": "Config",
+  "type": "object",
+  "properties": {},
+  "patternProperties": {
+    "^LABEL_": {
+      "type": "string",
+      "description": "Labels attached to metrics."
+    },
+    "^TOKEN_": {
+      "type": "string",
+      "writeOnly": true
+    }
+  },
+  "required": []
+}
+"#;
+    assert_eq!(expected, Config::json_schema());
+
+    let expected = r#"env:
+  # - name: LABEL_*
+  # - name: TOKEN_*
+"#;
+    assert_eq!(
+        expected,
+        render::kubernetes_env(&Config::requirements_model(), "my-app")
+    );
+}
+
+#[test]
+fn kubernetes_env() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from)]
+        database_url: String,
+        #[env(from, default = "8080")]
+        port: u16,
+        #[env(from)]
+        log_level: Option<String>,
+        #[env(from, sensitive)]
+        api_key: String,
+        #[env(from, sensitive)]
+        webhook_secret: Option<String>,
+    }
+
+    let expected = r#"env:
+  # - name: DATABASE_URL
+  #   value: ""  # required
+  - name: PORT
+    value: "8080"
+  # - name: LOG_LEVEL
+  #   value: ""
+  - name: API_KEY
+    valueFrom:
+      secretKeyRef:
+        name: "my-app"
+        key: API_KEY
+  - name: WEBHOOK_SECRET
+    valueFrom:
+      secretKeyRef:
+        name: "my-app"
+        key: WEBHOOK_SECRET
+        optional: true
+"#;
+
+    assert_eq!(
+        expected,
+        render::kubernetes_env(&Config::requirements_model(), "my-app")
+    );
+}
+
+#[test]
+fn compose_environment() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from)]
+        database_url: String,
+        #[env(from, default = "8080")]
+        port: u16,
+        #[env(from)]
+        log_level: Option<String>,
+        #[env(from, default = "hunter2", sensitive)]
+        password: String,
+        #[env(from, default = "$HOME/.cache")]
+        cache_dir: String,
+        #[env(from, default = "pa$word}")]
+        greeting: String,
+    }
+
+    let expected = r#"environment:
+  DATABASE_URL: "${DATABASE_URL:?DATABASE_URL is required}"
+  PORT: "${PORT:-8080}"
+  # LOG_LEVEL: "${LOG_LEVEL}"
+  # PASSWORD: "${PASSWORD}"
+  CACHE_DIR: "${CACHE_DIR:-$$HOME/.cache}"
+  # GREETING: "${GREETING}"
+"#;
+
+    assert_eq!(
+        expected,
+        render::compose_environment(&Config::requirements_model())
+    );
+}
@@ -0,0 +1,200 @@
+use std::path::PathBuf;
+
+use fromenv::{FieldPath, FromEnv, ParseResult, Requirement};
+
+fn level(s: &str) -> ParseResult<u8> {
+    Ok(s.parse()?)
+}
+
+/// Kafka connection settings.
+#[derive(FromEnv)]
+#[allow(unused)]
+pub struct KafkaConfig {
+    /// Comma separated list of brokers.
+    #[env(from = "KAFKA_BROKERS", alias = "KAFKA_HOSTS")]
+    brokers: String,
+}
+
+/// Application settings.
+#[derive(FromEnv)]
+#[allow(unused)]
+pub struct Config {
+    /// The port to listen on.
+    ///
+    /// Defaults to 8080.
+    #[env(from, default = "8080")]
+    port: u16,
+    #[env(from, with = level)]
+    log_level: Option<u8>,
+    #[env(from, os)]
+    data_dir: PathBuf,
+    #[env(from, file_fallback, sensitive)]
+    api_key: String,
+    #[env(nested, prefix = "EVENTS_")]
+    events: Option<KafkaConfig>,
+}
+
+#[test]
+fn requirements_model() {
+    let actual = Config::requirements_model_with_prefix("APP_");
+
+    let summary: Vec<_> = actual
+        .iter()
+        .map(|requirement| {
+            (
+                requirement.env_var.as_str(),
+                requirement.path.as_str(),
+                requirement.path.local(),
+                requirement.ty.as_str(),
+                requirement.parser.as_str(),
+                requirement.optional,
+                requirement.is_required(),
+            )
+        })
+        .collect();
+
+    let expected = vec![
+        (
+            "APP_PORT",
+            "Config.port",
+            "Config.port",
+            "u16",
+            "from_str",
+            false,
+            false,
+        ),
+        (
+            "APP_LOG_LEVEL",
+            "Config.log_level",
+            "Config.log_level",
+            "Option<u8>",
+            "level",
+            true,
+            false,
+        ),
+        (
+            "APP_DATA_DIR",
+            "Config.data_dir",
+            "Config.data_dir",
+            "PathBuf",
+            "os",
+            false,
+            true,
+        ),
+        (
+            "APP_API_KEY",
+            "Config.api_key",
+            "Config.api_key",
+            "String",
+            "from_str",
+            false,
+            true,
+        ),
+        (
+            "APP_EVENTS_KAFKA_BROKERS",
+            "Config.events.brokers",
+            "KafkaConfig.brokers",
+            "String",
+            "from_str",
+            true,
+            false,
+        ),
+    ];
+
+    assert_eq!(expected, summary);
+
+    let port = &actual[0];
+    assert_eq!(Some("8080"), port.default.as_deref());
+    assert_eq!(
+        Some("The port to listen on.\n\nDefaults to 8080."),
+        port.doc.as_deref()
+    );
+
+    assert_eq!(Some("Application settings."), port.struct_doc.as_deref());
+
+    let api_key = &actual[3];
+    assert!(api_key.sensitive);
+    assert!(api_key.file_fallback);
+    assert_eq!(None, api_key.doc);
+
+    let brokers = &actual[4];
+    assert_eq!(vec!["APP_EVENTS_KAFKA_HOSTS"], brokers.aliases);
+    assert_eq!(
+        Some("Kafka connection settings."),
+        brokers.struct_doc.as_deref()
+    );
+    assert_eq!(
+        FieldPath::new("Config.events.brokers", "KafkaConfig.brokers"),
+        brokers.path
+    );
+}
+
+#[test]
+fn requirements_renders_model() {
+    let expected = r#"# The port to listen on.
+#
+# Defaults to 8080.
+PORT=8080  # u16, optional (default)
+LOG_LEVEL=  # Option<u8>, optional
+DATA_DIR=  # PathBuf, required
+API_KEY=  # String, required, sensitive, or API_KEY_FILE
+
+## Kafka connection settings.
+# Comma separated list of brokers.
+EVENTS_KAFKA_BROKERS=  # String, optional, aliases: EVENTS_KAFKA_HOSTS
+"#;
+
+    assert_eq!(expected, Config::requirements());
+}
+
+#[test]
+fn sensitive_defaults_are_masked() {
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, default = "1234", sensitive)]
+        pin: u16,
+    }
+
+    let requirements: Vec<Requirement> = Config::requirements_model();
+
+    assert_eq!(Some("***"), requirements[0].default.as_deref());
+}
+
+#[test]
+fn sections_end_with_nested_struct() {
+    /// Database settings.
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct DatabaseConfig {
+        #[env(from = "DB_HOST")]
+        host: String,
+    }
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct CacheConfig {
+        #[env(from = "CACHE_URL")]
+        url: String,
+    }
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(nested)]
+        database: DatabaseConfig,
+        #[env(from)]
+        port: u16,
+        #[env(nested)]
+        cache: CacheConfig,
+    }
+
+    let expected = r#"## Database settings.
+DB_HOST=  # String, required
+
+PORT=  # u16, required
+CACHE_URL=  # String, required
+"#;
+
+    assert_eq!(expected, Config::requirements());
+}
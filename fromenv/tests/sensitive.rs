@@ -0,0 +1,131 @@
+use std::collections::HashMap;
+
+use fromenv::{FromEnv, FromEnvWarning, ParseResult};
+
+fn api_key(s: &str) -> ParseResult<String> {
+    if s.starts_with("sk-") {
+        Ok(s.to_owned())
+    } else {
+        Err(format!("expected '{s}' to start with 'sk-'").into())
+    }
+}
+
+#[derive(FromEnv, Debug)]
+#[allow(unused)]
+pub struct Config {
+    #[env(from = "API_KEY", with = api_key, sensitive)]
+    api_key: String,
+    #[env(from = "PIN", default = "1234", sensitive)]
+    pin: u16,
+}
+
+#[test]
+fn parse_errors_are_redacted() {
+    let source = HashMap::from([
+        ("API_KEY".to_owned(), "hunter2".to_owned()),
+        ("PIN".to_owned(), "secret-pin".to_owned()),
+    ]);
+
+    let expected = r#"2 configuration errors:
+  1. `Config.api_key`: Failed to parse 'API_KEY'="***": details redacted
+  2. `Config.pin`: Failed to parse 'PIN'="***": details redacted
+"#;
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    assert_eq!(expected, errors.to_string());
+
+    let debug = format!("{errors:?}");
+    assert!(!debug.contains("hunter2"));
+    assert!(!debug.contains("secret-pin"));
+}
+
+#[test]
+fn defaults_are_redacted() {
+    let source = HashMap::from([("API_KEY".to_owned(), "sk-123".to_owned())]);
+
+    let (_, warnings) = Config::from_source(&source).finalize_with_report().unwrap();
+
+    let expected = vec![FromEnvWarning::DefaultApplied {
+        path: fromenv::FieldPath::new("Config.pin", "Config.pin"),
+        env_var: "PIN".into(),
+        default: "***".into(),
+    }];
+
+    assert_eq!(expected, warnings.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn requirements_are_redacted() {
+    let expected = r#"API_KEY=  # String, required, sensitive
+PIN=***  # u16, optional (default), sensitive
+"#;
+
+    assert_eq!(expected, Config::requirements());
+}
+
+#[test]
+fn secrecy_types_are_sensitive() {
+    fn secret_key(s: &str) -> ParseResult<secrecy::SecretString> {
+        api_key(s).map(Into::into)
+    }
+
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "API_KEY", with = secret_key)]
+        api_key: Option<secrecy::SecretString>,
+    }
+
+    let source = HashMap::from([("API_KEY".to_owned(), "hunter2".to_owned())]);
+
+    let expected = r#"1 configuration error:
+  1. `Config.api_key`: Failed to parse 'API_KEY'="***": details redacted
+"#;
+    let actual = Config::from_source(&source).finalize().unwrap_err();
+
+    assert_eq!(expected, actual.to_string());
+    assert_eq!(
+        "API_KEY=  # Option<secrecy::SecretString>, optional, sensitive\n",
+        Config::requirements()
+    );
+}
+
+#[test]
+fn list_element_errors_are_redacted() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "API_KEYS", sep = ",", with = api_key, sensitive)]
+        api_keys: Vec<String>,
+    }
+
+    let source = HashMap::from([("API_KEYS".to_owned(), "sk-ok, supersecret".to_owned())]);
+
+    let expected = r#"1 configuration error:
+  1. `Config.api_keys`: Failed to parse 'API_KEYS'="***": element 1: details redacted
+"#;
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    assert_eq!(expected, errors.to_string());
+    assert!(!format!("{errors:?}").contains("supersecret"));
+}
+
+#[test]
+fn trimmed_values_are_redacted() {
+    #[derive(FromEnv, Debug)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from = "QUOTA", with = fromenv::byte_size, sensitive)]
+        quota: u64,
+    }
+
+    let source = HashMap::from([("QUOTA".to_owned(), " 918273645 parsecs ".to_owned())]);
+
+    let expected = r#"1 configuration error:
+  1. `Config.quota`: Failed to parse 'QUOTA'="***": details redacted
+"#;
+    let errors = Config::from_source(&source).finalize().unwrap_err();
+
+    assert_eq!(expected, errors.to_string());
+    assert!(!format!("{errors:?}").contains("918273645"));
+}
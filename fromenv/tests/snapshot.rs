@@ -0,0 +1,66 @@
+#![cfg(feature = "serde")]
+
+use fromenv::{FromEnv, Requirement, RequirementsDiff};
+
+mod v1 {
+    use super::*;
+
+    /// Server settings.
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        /// The port to listen on.
+        #[env(from, default = "8080")]
+        pub port: u16,
+        #[env(from, alias = "HOSTS", sep = ",")]
+        pub allowed_hosts: Vec<String>,
+        #[env(from, file_fallback, sensitive)]
+        pub api_key: Option<String>,
+    }
+}
+
+mod v2 {
+    use super::*;
+
+    #[derive(FromEnv)]
+    #[allow(unused)]
+    pub struct Config {
+        #[env(from, default = "8080")]
+        pub port: u16,
+        #[env(from, sep = ";")]
+        pub allowed_hosts: Vec<String>,
+    }
+}
+
+#[test]
+fn requirements_round_trip() {
+    let requirements = v1::Config::requirements_model();
+
+    let snapshot = serde_json::to_string_pretty(&requirements).unwrap();
+    let restored: Vec<Requirement> = serde_json::from_str(&snapshot).unwrap();
+
+    assert_eq!(requirements, restored);
+}
+
+#[test]
+fn snapshots_can_be_compared() {
+    let snapshot = serde_json::to_string(&v1::Config::requirements_model()).unwrap();
+    let previous: Vec<Requirement> = serde_json::from_str(&snapshot).unwrap();
+
+    let diff = RequirementsDiff::between(&previous, &v2::Config::requirements_model());
+
+    let expected = r#"breaking: 'ALLOWED_HOSTS' no longer reads 'HOSTS'
+breaking: 'ALLOWED_HOSTS' format changed from list separated by "," to list separated by ";"
+notable: 'API_KEY' is no longer used
+"#;
+
+    assert_eq!(expected, diff.to_string());
+}
+
+#[test]
+fn formats_are_tagged() {
+    let requirements = v1::Config::requirements_model();
+    let format = serde_json::to_value(&requirements[1].format).unwrap();
+
+    assert_eq!(serde_json::json!({ "kind": "list", "sep": "," }), format);
+}
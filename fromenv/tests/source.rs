@@ -0,0 +1,115 @@
+use std::collections::HashMap;
+
+use fromenv::FromEnv;
+
+#[test]
+fn hash_map_source() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from = "DATABASE_URL")]
+        db_url: String,
+        #[env(from = "PORT", default = "8080")]
+        port: u16,
+    }
+
+    let expected = Config {
+        db_url: "postgres://postgres@postgres/postgres".to_owned(),
+        port: 8080,
+    };
+
+    let source = HashMap::from([(
+        "DATABASE_URL".to_owned(),
+        "postgres://postgres@postgres/postgres".to_owned(),
+    )]);
+    let actual = Config::from_source(&source).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn closure_source() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from)]
+        port: u16,
+    }
+
+    let expected = Config { port: 8080 };
+
+    let actual = Config::from_source(&|key: &str| (key == "PORT").then(|| "8080".to_owned()))
+        .finalize()
+        .unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn source_is_threaded_through_nested_builders() {
+    use foo::{bar::KafkaConfig, baz::AppConfig};
+
+    mod foo {
+        pub mod bar {
+            use fromenv::FromEnv;
+
+            #[derive(FromEnv, Debug, PartialEq)]
+            pub struct KafkaConfig {
+                #[env(from = "KAFKA_BROKERS")]
+                pub brokers: String,
+                #[env(from = "KAFKA_TOPICS")]
+                pub topics: Option<String>,
+            }
+        }
+
+        pub mod baz {
+            use fromenv::FromEnv;
+
+            #[derive(FromEnv, Debug, PartialEq)]
+            pub struct AppConfig {
+                #[env(from)]
+                pub database_url: String,
+                #[env(nested)]
+                pub kafka: super::bar::KafkaConfig,
+            }
+        }
+    }
+
+    let expected = AppConfig {
+        database_url: "postgres://postgres@postgres/postgres".into(),
+        kafka: KafkaConfig {
+            brokers: "kafka:29092".into(),
+            topics: None,
+        },
+    };
+
+    let source = HashMap::from([
+        (
+            "DATABASE_URL".to_owned(),
+            "postgres://postgres@postgres/postgres".to_owned(),
+        ),
+        ("KAFKA_BROKERS".to_owned(), "kafka:29092".to_owned()),
+    ]);
+
+    // Ensure that the process environment isn't consulted.
+    let actual = temp_env::with_var("KAFKA_TOPICS", Some("orders"), || {
+        AppConfig::from_source(&source).finalize()
+    })
+    .unwrap();
+
+    assert_eq!(expected, actual);
+}
+
+#[test]
+fn builder_overrides_source_values() {
+    #[derive(FromEnv, Debug, PartialEq)]
+    pub struct Config {
+        #[env(from)]
+        port: u16,
+    }
+
+    let expected = Config { port: 56781 };
+
+    let source = HashMap::from([("PORT".to_owned(), "not-a-u16".to_owned())]);
+    let actual = Config::from_source(&source).port(56781).finalize().unwrap();
+
+    assert_eq!(expected, actual);
+}
@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(file_fallback)]
+    password: String,
+}
+
+fn main() {}
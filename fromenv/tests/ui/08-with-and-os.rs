@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, os, with = into)]
+    path: std::path::PathBuf,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, prefix = "REPLICA_")]
+    host: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(alias = "KAFKA_BROKERS")]
+    bootstrap_servers: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(sensitive)]
+    api_key: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(sep = ",")]
+    hosts: Vec<String>,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use std::ffi::OsString;
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, os, sep = ":")]
+    path: Vec<OsString>,
+    #[env(from, sep = "")]
+    hosts: Vec<String>,
+}
+
+fn main() {}
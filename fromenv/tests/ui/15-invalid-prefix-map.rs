@@ -0,0 +1,15 @@
+use std::collections::HashMap;
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, prefix_map = "LABEL_", default = "")]
+    labels: HashMap<String, String>,
+    #[env(prefix_map = "FEATURE_")]
+    features: Option<HashMap<String, bool>>,
+    #[env(from, lowercase)]
+    name: String,
+}
+
+fn main() {}
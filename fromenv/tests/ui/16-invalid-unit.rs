@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, unit = "ms")]
+    timeout: Duration,
+    #[env(from, with = fromenv::duration, unit = "weeks")]
+    interval: Duration,
+    #[env(from, with = duration, unit = "s")]
+    backoff: Duration,
+}
+
+fn duration(s: &str) -> fromenv::ParseResult<Duration> {
+    Ok(Duration::from_secs(s.parse()?))
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use std::collections::HashMap;
+
+use fromenv::FromEnv;
+
+#[derive(FromEnv)]
+pub struct Config {
+    #[env(from, sep = ",", kv_sep = ",")]
+    labels: HashMap<String, String>,
+}
+
+fn main() {}
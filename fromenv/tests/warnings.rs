@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+use fromenv::{FieldPath, FromEnv, FromEnvWarning};
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct KafkaConfig {
+    #[env(from = "KAFKA_BROKERS")]
+    brokers: String,
+    #[env(from = "KAFKA_TOPICS")]
+    topics: String,
+}
+
+#[derive(FromEnv, Debug, PartialEq)]
+pub struct Config {
+    #[env(from, default = "8080")]
+    port: u16,
+    #[env(from)]
+    log_filter: String,
+    #[env(nested)]
+    kafka: Option<KafkaConfig>,
+}
+
+#[test]
+fn collects_warnings() {
+    let source = HashMap::from([
+        ("LOG_FILTER".to_owned(), "".to_owned()),
+        ("KAFKA_BROKERS".to_owned(), "kafka:9092".to_owned()),
+    ]);
+
+    let (config, warnings) = Config::from_source(&source).finalize_with_report().unwrap();
+
+    assert_eq!(
+        Config {
+            port: 8080,
+            log_filter: "".into(),
+            kafka: None,
+        },
+        config
+    );
+
+    let expected = vec![
+        FromEnvWarning::DefaultApplied {
+            path: FieldPath::new("Config.port", "Config.port"),
+            env_var: "PORT".into(),
+            default: "8080".into(),
+        },
+        FromEnvWarning::EmptyValue {
+            path: FieldPath::new("Config.log_filter", "Config.log_filter"),
+            env_var: "LOG_FILTER".into(),
+        },
+        FromEnvWarning::OptionalGroupDropped {
+            path: FieldPath::new("Config.kafka", "Config.kafka"),
+        },
+    ];
+
+    assert_eq!(expected, warnings.into_iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn warnings_display() {
+    let source = HashMap::from([("LOG_FILTER".to_owned(), "".to_owned())]);
+
+    let expected = r#"3 configuration warnings:
+  1. `Config.port`: 'PORT' is not set, using default "8080"
+  2. `Config.log_filter`: 'LOG_FILTER' is set to an empty string
+  3. `Config.kafka`: Optional configuration was not loaded because it is incomplete
+"#;
+    let (_, warnings) = Config::from_source(&source).finalize_with_report().unwrap();
+
+    assert_eq!(expected, warnings.to_string());
+}
+
+#[test]
+fn overrides_do_not_warn() {
+    let (_, warnings) = Config::from_source(&HashMap::new())
+        .port(3000)
+        .log_filter("info".into())
+        .kafka(|kafka| kafka.brokers("kafka:9092".into()).topics("orders".into()))
+        .finalize_with_report()
+        .unwrap();
+
+    assert!(warnings.is_empty());
+}